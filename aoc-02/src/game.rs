@@ -0,0 +1,125 @@
+// Cyclic games like rock-paper-scissors. The moves are listed in a rules
+// table in cyclic order, where each move beats the (n-1)/2 moves before it
+// and loses to the (n-1)/2 after it, so with n moves (n odd):
+//     (me - opponent) mod n == 0           draw
+//     (me - opponent) mod n <= (n-1)/2     win
+//     otherwise                            loss
+// For plain rock-paper-scissors that is just: x beats (x-1) mod 3.
+
+pub struct MoveDef {
+    pub name: &'static str,
+    pub opponent: char, // symbol in the left column of the strategy guide
+    pub response: char, // symbol in the right column, when it names a move
+    pub score: i32
+}
+
+pub struct Rules {
+    pub name: &'static str,
+    pub moves: &'static [MoveDef]
+}
+
+pub const ROCK_PAPER_SCISSORS: Rules = Rules {
+    name: "rps",
+    moves: &[
+        MoveDef { name: "Rock", opponent: 'A', response: 'X', score: 1 },
+        MoveDef { name: "Paper", opponent: 'B', response: 'Y', score: 2 },
+        MoveDef { name: "Scissors", opponent: 'C', response: 'Z', score: 3 },
+    ]
+};
+
+// Letters and scores for the first three moves match the original game
+pub const ROCK_PAPER_SCISSORS_LIZARD_SPOCK: Rules = Rules {
+    name: "rpsls",
+    moves: &[
+        MoveDef { name: "Rock", opponent: 'A', response: 'X', score: 1 },
+        MoveDef { name: "Spock", opponent: 'E', response: 'V', score: 5 },
+        MoveDef { name: "Paper", opponent: 'B', response: 'Y', score: 2 },
+        MoveDef { name: "Lizard", opponent: 'D', response: 'U', score: 4 },
+        MoveDef { name: "Scissors", opponent: 'C', response: 'Z', score: 3 },
+    ]
+};
+
+pub const ALL_RULES: [&Rules; 2] = [&ROCK_PAPER_SCISSORS, &ROCK_PAPER_SCISSORS_LIZARD_SPOCK];
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win
+}
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+
+    pub fn score(&self) -> i32 {
+        return match self {
+            Outcome::Lose => 0,
+            Outcome::Draw => 3,
+            Outcome::Win => 6,
+        };
+    }
+
+    // Right column symbols, when the strategy guide names an outcome
+    pub fn from_symbol(symbol: char) -> Option<Outcome> {
+        return match symbol {
+            'X' => Some(Outcome::Lose),
+            'Y' => Some(Outcome::Draw),
+            'Z' => Some(Outcome::Win),
+            _ => None,
+        };
+    }
+}
+
+impl Rules {
+    pub fn count(&self) -> usize {
+        return self.moves.len();
+    }
+
+    pub fn outcome(&self, me: usize, opponent: usize) -> Outcome {
+        let n = self.count();
+        let difference = (me + n - opponent) % n;
+        if difference == 0 {
+            return Outcome::Draw;
+        }
+        if difference <= (n - 1) / 2 {
+            return Outcome::Win;
+        }
+        return Outcome::Lose;
+    }
+
+    // Where several moves give the outcome, the nearest in the cycle is used
+    pub fn move_for(&self, opponent: usize, outcome: Outcome) -> usize {
+        let n = self.count();
+        return match outcome {
+            Outcome::Lose => (opponent + n - 1) % n,
+            Outcome::Draw => opponent,
+            Outcome::Win => (opponent + 1) % n,
+        };
+    }
+
+    pub fn score(&self, me: usize, opponent: usize) -> i32 {
+        return self.moves[me].score + self.outcome(me, opponent).score();
+    }
+
+    pub fn parse_opponent(&self, symbol: char) -> Option<usize> {
+        return self.moves.iter().position(|m| m.opponent == symbol);
+    }
+
+    pub fn parse_response(&self, symbol: char) -> Option<usize> {
+        return self.moves.iter().position(|m| m.response == symbol);
+    }
+}
+
+/*-------------------------------------------------------------- get_rules - */
+
+// -rules [name] picks the game, rock-paper-scissors by default
+
+pub fn get_rules(name: Option<String>) -> &'static Rules {
+    let name = name.unwrap_or(String::from(ROCK_PAPER_SCISSORS.name));
+    return match ALL_RULES.iter().find(|r| r.name == name) {
+        Some(rules) => rules,
+        None => panic!("Unknown rules: [{}], expected one of {:?}", name,
+            ALL_RULES.iter().map(|r| r.name).collect::<Vec<&str>>()),
+    };
+}
+
+/*--------------------------------------------------------- End of game.rs - */
\ No newline at end of file
@@ -4,23 +4,22 @@ use std::fs::File;
 #[macro_use]
 mod applog;
 mod startup;
+mod game;
+mod report;
 
-#[derive(Debug, Copy, Clone)]
-enum RpsMove {
-    Rock,
-    Paper,
-    Scissors
-}
+use game::{Outcome, Rules};
 
+// Moves are indexes into the rules table
 #[derive(Debug, Copy, Clone)]
-struct RpsGame {
-    opponent: RpsMove,
-    me: RpsMove,
-    score: i32
+pub struct RpsGame {
+    pub opponent: usize,
+    pub response: char, // as written in the strategy guide
+    pub me: usize,
+    pub score: i32
 }
 impl Default for RpsGame {
     fn default () -> RpsGame {
-        RpsGame{opponent: RpsMove::Rock, me: RpsMove::Rock, score:0}
+        RpsGame{opponent: 0, response: 'X', me: 0, score:0}
     }
 }
 
@@ -54,89 +53,62 @@ fn part2(reader: BufReader<File>) {
 
 fn calculate_total_score(reader: BufReader<File>, part1: bool) {
 
+    let rules = game::get_rules(startup::value("rules"));
     let mut games: Vec<RpsGame> = vec![];
 
-    for line in reader.lines() {
+    for (index, line) in reader.lines().enumerate() {
         let line = line.unwrap();
-        let strategy: Vec<char> = line.chars().collect();
-        let mut game: RpsGame = RpsGame::default();
-        game.opponent = match strategy[0] {
-            'A' => RpsMove::Rock,
-            'B' => RpsMove::Paper,
-            _ => RpsMove::Scissors,
-        };
-        game.me = get_my_move(strategy[2], game.opponent, part1);
-        game.score = compute_rps_score(&game);
-        games.push(game);
+        match read_game(&line, index + 1, rules, part1) {
+            Ok(game) => games.push(game),
+            Err(e) => {
+                applog!("{}", e);
+                return;
+            }
+        }
 
         //applog!("Line: {}, score: {}", line, game.score);
     }
 
     let mut score: i32 = 0;
-    for game in games {
+    for game in games.iter() {
         score += game.score;
     }
 
     applog!("Total score: {}", score);
+
+    if startup::is("report") {
+        report::print_report(rules, &games);
+    }
 }
 
-/*------------------------------------------------------ compute_rps_score - */
+/*-------------------------------------------------------------- read_game - */
 
-fn compute_rps_score(game: &RpsGame) -> i32 {
-    let basic_score = match game.me {
-        RpsMove::Rock => 1,
-        RpsMove::Paper => 2,
-        _ => 3,
-    };
+// Assumes format: [opponent] [response], e.g. "A Y". The response is my move
+// in part 1 and the outcome I need in part 2.
 
-    let result_score = match game.me {
-        RpsMove::Rock => match game.opponent {
-            RpsMove::Rock => 3,
-            RpsMove::Paper => 0,
-            _ => 6,
-        },
-        RpsMove::Paper => match game.opponent {
-            RpsMove::Rock => 6,
-            RpsMove::Paper => 3,
-            _ => 0,
-        },
-        _ => match game.opponent {
-            RpsMove::Rock => 0,
-            RpsMove::Paper => 6,
-            _ => 3,
-        },
-    };
+fn read_game(line: &str, line_number: usize, rules: &Rules, part1: bool) -> Result<RpsGame, String> {
 
-    return basic_score + result_score;
-}
+    let strategy: Vec<char> = line.trim_end().chars().collect();
+    if strategy.len() != 3 || strategy[1] != ' ' {
+        return Err(format!("Line {}: expected two symbols like \"A Y\", got [{}]", line_number, line));
+    }
 
-/*------------------------------------------------------------ get_my_move - */
+    let opponent = match rules.parse_opponent(strategy[0]) {
+        Some(opponent) => opponent,
+        None => return Err(format!("Line {}: unknown opponent move [{}]", line_number, strategy[0])),
+    };
 
-fn get_my_move(strategy: char, opponent: RpsMove, part1: bool) -> RpsMove {
-    let my_move: RpsMove = 
-        if part1 {
-            match strategy {
-                'X' => RpsMove::Rock,
-                'Y' => RpsMove::Paper,
-                _ => RpsMove::Scissors,
-            }
-        } else {
-            match strategy {
-                'X' => match opponent { // Need to lose
-                    RpsMove::Rock => RpsMove::Scissors,
-                    RpsMove::Paper => RpsMove::Rock,
-                    _ => RpsMove::Paper,
-                },
-                'Y' => opponent, // Need to draw
-                _ => match opponent { // Need to win
-                    RpsMove::Rock => RpsMove::Paper,
-                    RpsMove::Paper => RpsMove::Scissors,
-                    _ => RpsMove::Rock,
-                },
-            }
-        };
+    let me = if part1 {
+        rules.parse_response(strategy[2])
+    } else {
+        Outcome::from_symbol(strategy[2]).map(|outcome| rules.move_for(opponent, outcome))
+    };
+    let me = match me {
+        Some(me) => me,
+        None => return Err(format!("Line {}: unknown response [{}]", line_number, strategy[2])),
+    };
 
-    return my_move;
+    return Ok(RpsGame { opponent, response: strategy[2], me, score: rules.score(me, opponent) });
 }
 
 /*--------------------------------------------------------- End of main.rs - */
\ No newline at end of file
@@ -0,0 +1,116 @@
+use crate::applog;
+use crate::RpsGame;
+use crate::game::{Outcome, Rules};
+
+// How good is the strategy guide? Breaks the games down by outcome, by move
+// and by round, then compares the total with the best possible score and
+// with every other way the right column could have been read.
+
+pub fn print_report(rules: &Rules, games: &Vec<RpsGame>) {
+
+    applog!("By outcome:");
+    for outcome in Outcome::ALL {
+        let matching: Vec<&RpsGame> = games.iter()
+            .filter(|g| rules.outcome(g.me, g.opponent) == outcome)
+            .collect();
+        let points: i32 = matching.iter().map(|g| g.score).sum();
+        applog!("  {:<8} {:>6} rounds, {:>7} points", format!("{:?}", outcome), matching.len(), points);
+    }
+
+    applog!("By move played:");
+    for (m, definition) in rules.moves.iter().enumerate() {
+        let matching: Vec<&RpsGame> = games.iter().filter(|g| g.me == m).collect();
+        let points: i32 = matching.iter().map(|g| g.score).sum();
+        applog!("  {:<8} {:>6} rounds, {:>7} points", definition.name, matching.len(), points);
+    }
+
+    applog!("By round:");
+    for (i, game) in games.iter().enumerate() {
+        applog!("  Round {:>4}: {} {}, {} v {}, {:?}, {} points",
+            i + 1, rules.moves[game.opponent].opponent, game.response,
+            rules.moves[game.me].name, rules.moves[game.opponent].name,
+            rules.outcome(game.me, game.opponent), game.score);
+    }
+
+    let total: i32 = games.iter().map(|g| g.score).sum();
+    let optimal: i32 = games.iter()
+        .map(|g| (0..rules.count()).map(|m| rules.score(m, g.opponent)).max().unwrap())
+        .sum();
+    let percentage = if optimal == 0 {0.0} else {100.0 * total as f64 / optimal as f64};
+    applog!("Guide score: {}, optimal score: {} ({:.1}% of optimal)", total, optimal, percentage);
+
+    print_interpretations(rules, games);
+}
+
+/*-------------------------------------------------- print_interpretations - */
+
+// Each symbol used in the right column is read either as a move (part 1) or
+// as an outcome (part 2), in every possible assignment. Best first.
+
+fn print_interpretations(rules: &Rules, games: &Vec<RpsGame>) {
+
+    let mut symbols: Vec<char> = games.iter().map(|g| g.response).collect();
+    symbols.sort();
+    symbols.dedup();
+    let symbol_index = |c: char| symbols.iter().position(|s| *s == c).unwrap();
+
+    let mut interpretations: Vec<(i32, String)> = vec![];
+
+    for assignment in get_permutations(symbols.len(), rules.count()) {
+        let score: i32 = games.iter()
+            .map(|g| rules.score(assignment[symbol_index(g.response)], g.opponent))
+            .sum();
+        let is_part1 = symbols.iter().zip(assignment.iter())
+            .all(|(s, m)| rules.parse_response(*s) == Some(*m));
+        let labels: Vec<String> = symbols.iter().zip(assignment.iter())
+            .map(|(s, m)| format!("{}={}", s, rules.moves[*m].name))
+            .collect();
+        interpretations.push((score, format!("{}{}", labels.join(", "), if is_part1 {" (part 1)"} else {""})));
+    }
+
+    for assignment in get_permutations(symbols.len(), Outcome::ALL.len()) {
+        let score: i32 = games.iter()
+            .map(|g| {
+                let outcome = Outcome::ALL[assignment[symbol_index(g.response)]];
+                rules.score(rules.move_for(g.opponent, outcome), g.opponent)
+            })
+            .sum();
+        let is_part2 = symbols.iter().zip(assignment.iter())
+            .all(|(s, o)| Outcome::from_symbol(*s) == Some(Outcome::ALL[*o]));
+        let labels: Vec<String> = symbols.iter().zip(assignment.iter())
+            .map(|(s, o)| format!("{}={:?}", s, Outcome::ALL[*o]))
+            .collect();
+        interpretations.push((score, format!("{}{}", labels.join(", "), if is_part2 {" (part 2)"} else {""})));
+    }
+
+    interpretations.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+    applog!("Score under each reading of {:?}:", symbols);
+    for (score, label) in interpretations {
+        applog!("  {:>7}  {}", score, label);
+    }
+}
+
+/*------------------------------------------------------- get_permutations - */
+
+// Every ordered selection of k distinct values from 0..n
+
+fn get_permutations(k: usize, n: usize) -> Vec<Vec<usize>> {
+
+    if k == 0 {
+        return vec![vec![]];
+    }
+
+    let mut permutations: Vec<Vec<usize>> = vec![];
+    for shorter in get_permutations(k - 1, n) {
+        for value in (0..n).filter(|v| !shorter.contains(v)) {
+            let mut permutation = shorter.clone();
+            permutation.push(value);
+            permutations.push(permutation);
+        }
+    }
+
+    return permutations;
+}
+
+/*------------------------------------------------------- End of report.rs - */
\ No newline at end of file
@@ -4,6 +4,7 @@ use std::fs::File;
 #[macro_use]
 mod applog;
 mod startup;
+mod rucksack;
 
 /*------------------------------------------------------------------- main - */
 
@@ -24,22 +25,30 @@ fn main() {
 fn part1(reader: BufReader<File>) {
     let mut priorities: Vec<i32> = vec![];
 
-    for line in reader.lines() {
+    for (index, line) in reader.lines().enumerate() {
         let line = line.unwrap();
+
+        // Items are checked first, so the split below is on ASCII only
+        if let Err(e) = rucksack::get_item_mask(&line) {
+            applog!("Line {}: {}", index + 1, e);
+            return;
+        }
+        if line.len()%2 != 0 {
+            applog!("Line {}: odd number of items, can't split into compartments", index + 1);
+            return;
+        }
+
         let compartment_len = line.len()/2;
         let compartment1 = &line[..compartment_len];
         let compartment2 = &line[compartment_len..];
 
-        let mut item: char = '\0';
-        for c in compartment1.chars() {
-            if compartment2.contains(c) {
-                item = c;
-                break;
+        match rucksack::find_shared_item(&[compartment1, compartment2]) {
+            Ok(item) => priorities.push(get_item_priority(item)),
+            Err(e) => {
+                applog!("Line {}: {}", index + 1, e);
+                return;
             }
         }
-
-        let priority = get_item_priority(item);
-        priorities.push(priority);
     }
 
     let sum: i32 = priorities.iter().sum();
@@ -48,29 +57,40 @@ fn part1(reader: BufReader<File>) {
 
 /*------------------------------------------------------------------ part2 - */
 
+// Elves are in groups of three, or -group [N]
+
 fn part2(reader: BufReader<File>) {
+    let group_size: usize = match startup::value("group") {
+        Some(g) => match g.parse::<usize>() {
+            Ok(size) if size > 0 => size,
+            _ => panic!("Invalid group size: [{}]", g),
+        },
+        None => 3,
+    };
+
     let mut priorities: Vec<i32> = vec![];
-    let mut elf_group: [String; 3] = Default::default();
+    let mut elf_group: Vec<String> = vec![];
 
     for (line_number, line) in reader.lines().enumerate() {
-        let line = line.unwrap();
-        let elf_index = line_number%3;
-        elf_group[elf_index] = line;
-        if elf_index==2 {
-            let (elf1, elf2, elf3) = (&elf_group[0], &elf_group[1], &elf_group[2]);
-            let mut item: char = '\0';
-            for c in elf1.chars() {
-                if elf2.contains(c) && elf3.contains(c) {
-                    item = c;
-                    break;
+        elf_group.push(line.unwrap());
+        if elf_group.len()==group_size {
+            let rucksacks: Vec<&str> = elf_group.iter().map(|r| r.as_str()).collect();
+            match rucksack::find_shared_item(&rucksacks) {
+                Ok(item) => priorities.push(get_item_priority(item)),
+                Err(e) => {
+                    applog!("Lines {}-{}: {}", line_number + 2 - group_size, line_number + 1, e);
+                    return;
                 }
             }
-
-            let priority = get_item_priority(item);
-            priorities.push(priority);
+            elf_group.clear();
         }
     }
 
+    if !elf_group.is_empty() {
+        applog!("Last group only has {} of {} elves", elf_group.len(), group_size);
+        return;
+    }
+
     let sum: i32 = priorities.iter().sum();
     applog!("Sum of priorities: {}", sum);
 }
@@ -0,0 +1,56 @@
+use crate::get_item_priority;
+
+// A set of items as a 52-bit mask, where bit (priority - 1) is set if the
+// item is present, so finding what rucksacks share is a bitwise AND.
+
+/*---------------------------------------------------------- get_item_mask - */
+
+pub fn get_item_mask(items: &str) -> Result<u64, String> {
+
+    let mut mask: u64 = 0;
+    for item in items.chars() {
+        let priority = get_item_priority(item);
+        if priority == 0 {
+            return Err(format!("invalid item [{}]", item));
+        }
+        mask |= 1 << (priority - 1);
+    }
+
+    return Ok(mask);
+}
+
+/*------------------------------------------------------- find_shared_item - */
+
+// The one item in every list; it's an error if there are none or several
+
+pub fn find_shared_item(item_lists: &[&str]) -> Result<char, String> {
+
+    let mut shared: u64 = u64::MAX;
+    for items in item_lists {
+        shared &= get_item_mask(items)?;
+    }
+
+    return match shared.count_ones() {
+        0 => Err(String::from("no shared item")),
+        1 => Ok(get_item(shared.trailing_zeros() as i32 + 1)),
+        _ => {
+            let items: String = (0..52).filter(|bit| shared & (1 << bit) != 0)
+                .map(|bit| get_item(bit + 1))
+                .collect();
+            Err(format!("more than one shared item [{}]", items))
+        },
+    };
+}
+
+/*--------------------------------------------------------------- get_item - */
+
+// Inverse of get_item_priority
+
+fn get_item(priority: i32) -> char {
+    if priority <= 26 {
+        return (b'a' + (priority - 1) as u8) as char;
+    }
+    return (b'A' + (priority - 27) as u8) as char;
+}
+
+/*----------------------------------------------------- End of rucksack.rs - */
\ No newline at end of file
@@ -47,6 +47,16 @@ pub fn is_part1() -> bool {
     }
 }
 
+/*------------------------------------------------------------------ value - */
+
+// Returns the argument following -[name], e.g. "-group 3"
+
+pub fn value(name: &str) -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|a| *a == format!("-{}", name))?;
+    return args.get(index+1).cloned();
+}
+
 /*---------------------------------------------------------- get_start_time - */
 
 pub fn get_start_time() -> Instant {
@@ -4,6 +4,9 @@ use std::fs::File;
 #[macro_use]
 mod applog;
 mod startup;
+mod sections;
+
+use sections::SectionRange;
 
 /*------------------------------------------------------------------- main - */
 
@@ -33,34 +36,80 @@ fn part2(reader: BufReader<File>) {
 
 /*----------------------------------------------------------- get_overlaps - */
 
+// -pairs also logs each pair's overlap
+
 fn get_overlaps(reader: BufReader<File>) {
     let mut full_overlaps: i32 = 0;
     let mut partial_overlaps: i32 = 0;
+    let mut wasted_work: u64 = 0;
+    let mut assignments: Vec<SectionRange> = vec![];
+    let show_pairs = startup::is("pairs");
 
-    for line in reader.lines() {
+    for (index, line) in reader.lines().enumerate() {
         let line = line.unwrap();
-        let ranges: Vec<&str> = line.split(',').collect();
-        let range1: Vec<&str> = ranges[0].split('-').collect();
-        let range2: Vec<&str> = ranges[1].split('-').collect();
-
-        // Each line: s1-e1,s2-e2
-        let s1 = range1[0].parse::<i32>().unwrap();
-        let e1 = range1[1].parse::<i32>().unwrap();
-        let s2 = range2[0].parse::<i32>().unwrap();
-        let e2 = range2[1].parse::<i32>().unwrap();
+        let (range1, range2) = match sections::parse_pair(&line, index + 1) {
+            Ok(pair) => pair,
+            Err(e) => {
+                applog!("{}", e);
+                return;
+            }
+        };
 
         // Full overlaps:       Partial overlaps:
         // ...s1........e1...   ...s1.....e1......
         // ......s2..e2......   ......s2.....e2...
 
-        if (s1<=s2 && e1>=e2) || (s2<=s1 && e2>=e1) {
+        let overlap = range1.intersection(&range2);
+        if range1.contains(&range2) || range2.contains(&range1) {
             full_overlaps += 1;
-        } else if (s1<=s2 && s2<=e1 && e2>=e1) || (s2<=s1 && s1<=e2 && e1>=e2) {
+        } else if overlap.is_some() {
             partial_overlaps += 1;
         }
+
+        // Sections in the overlap get cleaned twice
+        let wasted = overlap.map(|o| o.len()).unwrap_or(0);
+        wasted_work += wasted as u64;
+
+        if show_pairs {
+            let overlap = overlap.map(|o| o.to_string()).unwrap_or(String::from("none"));
+            applog!("Line {}: {},{} overlap={}, wasted={}", index + 1, range1, range2, overlap, wasted);
+        }
+
+        assignments.push(range1);
+        assignments.push(range2);
     }
 
     applog!("Overlaps: full={}, partial={}, total={}", full_overlaps, partial_overlaps, full_overlaps+partial_overlaps);
+    applog!("Wasted work: {} section(s) cleaned twice", wasted_work);
+
+    report_coverage(&assignments);
+}
+
+/*-------------------------------------------------------- report_coverage - */
+
+// Uncovered sections are those up to the highest assigned one
+
+fn report_coverage(assignments: &Vec<SectionRange>) {
+    let coverage = sections::get_coverage(assignments);
+    let most = coverage.iter().map(|(_, elves)| *elves).max().unwrap_or(0);
+
+    let busiest = sections::get_ranges(&coverage, |elves| elves == most);
+    let uncovered = sections::get_ranges(&coverage, |elves| elves == 0);
+
+    applog!("Most covered: {} elves on {}", most, format_ranges(&busiest));
+    applog!("Not covered: {}", format_ranges(&uncovered));
+}
+
+/*---------------------------------------------------------- format_ranges - */
+
+fn format_ranges(ranges: &Vec<SectionRange>) -> String {
+    if ranges.is_empty() {
+        return String::from("none");
+    }
+    let ranges: Vec<String> = ranges.iter()
+        .map(|r| if r.len() == 1 {r.start.to_string()} else {r.to_string()})
+        .collect();
+    return ranges.join(", ");
 }
 
 /*--------------------------------------------------------- End of main.rs - */
\ No newline at end of file
@@ -0,0 +1,126 @@
+use std::fmt;
+
+// An inclusive range of section IDs, start <= end
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SectionRange {
+    pub start: u32,
+    pub end: u32
+}
+impl SectionRange {
+    pub fn len(&self) -> u32 {
+        return self.end - self.start + 1;
+    }
+
+    pub fn contains(&self, other: &SectionRange) -> bool {
+        return self.start <= other.start && other.end <= self.end;
+    }
+
+    pub fn intersection(&self, other: &SectionRange) -> Option<SectionRange> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        if start > end {
+            return None;
+        }
+        return Some(SectionRange { start, end });
+    }
+}
+impl fmt::Display for SectionRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/*------------------------------------------------------------- parse_pair - */
+
+// Assumes format: a-b,c-d
+
+pub fn parse_pair(line: &str, line_number: usize) -> Result<(SectionRange, SectionRange), String> {
+
+    let ranges: Vec<&str> = line.trim_end().split(',').collect();
+    if ranges.len() != 2 {
+        return Err(format!("Line {}: expected two ranges like 2-4,6-8, got [{}]", line_number, line));
+    }
+
+    let first = parse_range(ranges[0]).map_err(|e| format!("Line {}: {}", line_number, e))?;
+    let second = parse_range(ranges[1]).map_err(|e| format!("Line {}: {}", line_number, e))?;
+
+    return Ok((first, second));
+}
+
+/*------------------------------------------------------------ parse_range - */
+
+fn parse_range(range: &str) -> Result<SectionRange, String> {
+
+    let bounds: Vec<&str> = range.split('-').collect();
+    if bounds.len() != 2 {
+        return Err(format!("expected a range like 2-4, got [{}]", range));
+    }
+
+    let parse = |bound: &str| bound.parse::<u32>()
+        .map_err(|_| format!("invalid section [{}] in range [{}]", bound, range));
+    let (start, end) = (parse(bounds[0])?, parse(bounds[1])?);
+
+    if start == 0 {
+        return Err(format!("range [{}] starts at 0, sections are numbered from 1", range));
+    }
+    if start > end {
+        return Err(format!("range [{}] starts after it ends", range));
+    }
+
+    return Ok(SectionRange { start, end });
+}
+
+/*----------------------------------------------------------- get_coverage - */
+
+// Splits sections 1 up to the highest assigned into spans covered by the same
+// number of elves, from a sorted list of where ranges start and end. Section
+// IDs can be huge, so the sections themselves are never listed.
+
+pub fn get_coverage(ranges: &[SectionRange]) -> Vec<(SectionRange, u32)> {
+
+    // (section, change in elves from that section on); u64 as end+1 may
+    // not fit a u32
+    let mut events: Vec<(u64, i64)> = vec![];
+    for range in ranges {
+        events.push((range.start as u64, 1));
+        events.push((range.end as u64 + 1, -1));
+    }
+    events.sort();
+
+    let mut coverage: Vec<(SectionRange, u32)> = vec![];
+    let mut elves: i64 = 0;
+    let mut from: u64 = 1;
+    for (section, change) in events {
+        if section > from {
+            let span = SectionRange { start: from as u32, end: (section - 1) as u32 };
+            coverage.push((span, elves as u32));
+            from = section;
+        }
+        elves += change;
+    }
+
+    return coverage;
+}
+
+/*------------------------------------------------------------- get_ranges - */
+
+// Spans where the predicate holds, with neighbours merged
+
+pub fn get_ranges(coverage: &[(SectionRange, u32)], predicate: impl Fn(u32) -> bool) -> Vec<SectionRange> {
+
+    let mut ranges: Vec<SectionRange> = vec![];
+    for (span, elves) in coverage {
+        if !predicate(*elves) {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(range) if range.end as u64 + 1 == span.start as u64 => range.end = span.end,
+            _ => ranges.push(*span),
+        }
+    }
+
+    return ranges;
+}
+
+/*----------------------------------------------------- End of sections.rs - */
\ No newline at end of file
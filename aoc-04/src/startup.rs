@@ -47,6 +47,13 @@ pub fn is_part1() -> bool {
     }
 }
 
+/*--------------------------------------------------------------------- is - */
+
+pub fn is(name: &str) -> bool {
+    let args: Vec<String> = env::args().collect();
+    return args.contains(&format!("-{}", name));
+}
+
 /*---------------------------------------------------------- get_start_time - */
 
 pub fn get_start_time() -> Instant {
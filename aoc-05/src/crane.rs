@@ -0,0 +1,109 @@
+use crate::Instruction;
+use crate::startup;
+use crate::stacks::Stacks;
+
+// Every crane lifts some number of crates off the top of one stack and sets
+// them down, in the same order, on top of another. A model only decides how
+// many it can lift at once; a full move is repeated lifts until done.
+
+pub trait Crane {
+    fn name(&self) -> String;
+
+    // Most crates moved in one lift, None for no limit
+    fn capacity(&self) -> Option<usize>;
+
+    fn perform(&self, stacks: &mut Stacks, instruction: &Instruction) -> Result<(), String> {
+        validate(stacks, instruction)?;
+
+        let lift = self.capacity().unwrap_or(instruction.number).max(1);
+        let mut remaining = instruction.number;
+        while remaining > 0 {
+            let count = remaining.min(lift);
+            let items = stacks.take(instruction.from, count);
+            stacks.put(instruction.to, items);
+            remaining -= count;
+        }
+        return Ok(());
+    }
+}
+
+// One crate at a time, so a move reverses the crates' order
+pub struct CrateMover9000;
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        return String::from("CrateMover 9000");
+    }
+    fn capacity(&self) -> Option<usize> {
+        return Some(1);
+    }
+}
+
+// Any number at once, so a move keeps the crates' order
+pub struct CrateMover9001;
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        return String::from("CrateMover 9001");
+    }
+    fn capacity(&self) -> Option<usize> {
+        return None;
+    }
+}
+
+// Up to a fixed number at once
+pub struct LimitedCrane {
+    pub capacity: usize
+}
+impl Crane for LimitedCrane {
+    fn name(&self) -> String {
+        return format!("Crane moving up to {} at a time", self.capacity);
+    }
+    fn capacity(&self) -> Option<usize> {
+        return Some(self.capacity);
+    }
+}
+
+/*-------------------------------------------------------------- get_crane - */
+
+// -crane 9000|9001 picks a model, -crane-capacity [N] a crane that moves at
+// most N crates at once. Otherwise part 1 uses the 9000 and part 2 the 9001.
+
+pub fn get_crane(part1: bool) -> Box<dyn Crane> {
+
+    if let Some(capacity) = startup::value("crane-capacity") {
+        let capacity = match capacity.parse::<usize>() {
+            Ok(c) if c > 0 => c,
+            _ => panic!("Invalid crane capacity: [{}]", capacity),
+        };
+        return Box::new(LimitedCrane { capacity });
+    }
+
+    let model = startup::value("crane").unwrap_or(String::from(if part1 {"9000"} else {"9001"}));
+    return match model.as_str() {
+        "9000" => Box::new(CrateMover9000),
+        "9001" => Box::new(CrateMover9001),
+        _ => panic!("Unknown crane model: [{}], expected 9000 or 9001", model),
+    };
+}
+
+/*--------------------------------------------------------------- validate - */
+
+fn validate(stacks: &Stacks, instruction: &Instruction) -> Result<(), String> {
+
+    for stack in [instruction.from, instruction.to] {
+        if stack == 0 || stack > stacks.len() {
+            return Err(format!("Line {}: no stack {}, there are {} stacks",
+                instruction.line, stack, stacks.len()));
+        }
+    }
+
+    let height = stacks.height(instruction.from);
+    if instruction.number > height {
+        let contents = if height == 0 {String::from("it is empty")} else {format!("it only has {}", height)};
+        return Err(format!("Line {}: can't move {} crate(s) from stack {}, {}",
+            instruction.line, instruction.number, instruction.from, contents));
+    }
+
+    return Ok(());
+}
+
+/*-------------------------------------------------------- End of crane.rs - */
\ No newline at end of file
@@ -1,19 +1,31 @@
 use std::io::{BufRead, BufReader};
 use std::fs::File;
+use std::fmt;
 
 #[macro_use]
 mod applog;
 mod startup;
+mod stacks;
+mod crane;
+
+use stacks::Stacks;
+use crane::Crane;
 
 #[derive(Debug, Copy, Clone)]
-struct Instruction {
-    number: i32,
-    from: usize,
-    to: usize,
+pub struct Instruction {
+    pub number: usize,
+    pub from: usize,
+    pub to: usize,
+    pub line: usize, // in the input file, for error messages
 }
 impl Default for Instruction {
     fn default () -> Instruction {
-        Instruction{number: 0, from: 0, to: 0}
+        Instruction{number: 0, from: 0, to: 0, line: 0}
+    }
+}
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.number, self.from, self.to)
     }
 }
 
@@ -47,111 +59,100 @@ fn part2(reader: BufReader<File>) {
 
 fn perform_container_operations(reader: BufReader<File>, part1: bool) {
 
-    let mut container_stacks: Vec<Vec<char>> = vec![];
+    let mut drawing: Vec<String> = vec![];
     let mut instructions: Vec<Instruction> = vec![];
     let mut reading_instructions: bool = false;
    
     // Read the file
-    for line in reader.lines() {
+    for (index, line) in reader.lines().enumerate() {
         let line = line.unwrap();
 
         // Have we reached the end of the stacks?
-        if line.len()==0 {
+        if line.trim().is_empty() {
             reading_instructions = true;
             continue;
         }
 
         // What are we reading?
         if reading_instructions {
-            read_instruction_record(&line, &mut instructions);
+            match read_instruction_record(&line, index + 1) {
+                Ok(instruction) => instructions.push(instruction),
+                Err(e) => {
+                    applog!("{}", e);
+                    return;
+                }
+            }
         } else {
-            read_container_stack_record(&line, &mut container_stacks);
+            drawing.push(line);
         }
     }
 
-    follow_instructions(&instructions, &mut container_stacks, !part1);
+    let mut container_stacks = match stacks::parse_drawing(&drawing) {
+        Ok(container_stacks) => container_stacks,
+        Err(e) => {
+            applog!("{}", e);
+            return;
+        }
+    };
+
+    let crane = crane::get_crane(part1);
+    applog!("Using the {}", crane.name());
 
-    // Get top of the stacks
-    let mut top_of_stacks: String = String::new();
-    let num_stacks=container_stacks.len();
-    for stack_index in 0..num_stacks {
-        top_of_stacks.push(container_stacks[stack_index].pop().unwrap());
+    if let Err(e) = follow_instructions(&instructions, &mut container_stacks, crane.as_ref()) {
+        applog!("{}", e);
+        return;
     }
-    applog!("Top of stacks: {}", top_of_stacks);
+
+    applog!("Top of stacks: {}", container_stacks.get_tops());
 }
 
 /*------------------------------------------------ read_instruction_record - */
 
-fn read_instruction_record (line: &str, instructions: &mut Vec<Instruction>) {
-    let tokens: Vec<&str> = line.split(" ").collect();
-    let instruction = Instruction {
-        number: tokens[1].parse::<i32>().unwrap(),
-        from: tokens[3].parse::<usize>().unwrap(),
-        to: tokens[5].parse::<usize>().unwrap()
-    };
-
-    instructions.push(instruction);
-}
-
-/*-------------------------------------------- read_container_stack_record - */
+// Assumes format: move [number] from [stack] to [stack]
 
-fn read_container_stack_record(line: &str, container_stacks: &mut Vec<Vec<char>>) {
-    // populate container_stacks if not already done
-    let num_stacks = (line.len()+1)/4;
-    while container_stacks.len() < num_stacks {
-        let empty_stack: Vec<char> = vec![];
-        container_stacks.push(empty_stack);
+fn read_instruction_record (line: &str, line_number: usize) -> Result<Instruction, String> {
+    let tokens: Vec<&str> = line.split(" ").collect();
+    if tokens.len() != 6 || tokens[0] != "move" || tokens[2] != "from" || tokens[4] != "to" {
+        return Err(format!("Line {}: expected \"move N from A to B\", got [{}]", line_number, line));
     }
 
-    // Iterate over stacks - see which have a crate at this level
-    for stack_index in 0..num_stacks {
-        let stack_item = line.chars().nth(1 + stack_index*4).unwrap();
-        if stack_item == '1' { // Have we reached the stack numbers?
-            break;
-        }
+    let parse = |token: &str| token.parse::<usize>()
+        .map_err(|_| format!("Line {}: invalid number [{}] in [{}]", line_number, token, line));
 
-        if stack_item != ' ' { // Is stack populated at this level?
-            container_stacks[stack_index].push(stack_item);
-        }
-    }
+    return Ok(Instruction {
+        number: parse(tokens[1])?,
+        from: parse(tokens[3])?,
+        to: parse(tokens[5])?,
+        line: line_number
+    });
 }
 
 /*---------------------------------------------------- follow_instructions - */
 
-fn follow_instructions(instructions: &Vec<Instruction>, container_stacks: &mut Vec<Vec<char>>, multi_move: bool) {
+// The stacks are drawn before and after; -step draws them after every move
 
-    // First thing: reverse all the stacks - we want to remove last added item first 
-    // (bottom of the stack)
-    let num_stacks = container_stacks.len();
-    for stack_index in 0..num_stacks {
-        container_stacks[stack_index].reverse();
-    }
+fn follow_instructions(instructions: &Vec<Instruction>, container_stacks: &mut Stacks, crane: &dyn Crane) -> Result<(), String> {
+
+    let step = startup::is("step");
 
-    applog!("Stacks {:?}", container_stacks);
+    applog!("Starting stacks:");
+    println!("{}\n", container_stacks);
 
     // Follow the instructions
     for instruction in instructions {
-        applog!("{:?}", instruction);
-        
-        // Move specified number of crates
-        if multi_move {
-            let mut staging: Vec<char> = vec![];
-            for _i in 0..instruction.number {
-                let stack_item = container_stacks[instruction.from-1].pop().unwrap();
-                staging.push(stack_item);
-            }
-            for _i in 0..instruction.number {
-                let stack_item = staging.pop().unwrap();
-                container_stacks[instruction.to-1].push(stack_item);
-            }
-        } else {
-            for _i in 0..instruction.number {
-                let stack_item = container_stacks[instruction.from-1].pop().unwrap();
-                container_stacks[instruction.to-1].push(stack_item);
-            }
+        crane.perform(container_stacks, instruction)?;
+        if step {
+            applog!("After line {}: {}", instruction.line, instruction);
+            println!("{}\n", container_stacks);
         }
-        applog!("Stacks {:?}", container_stacks);
     }
+
+    if !step {
+        applog!("Final stacks:");
+        println!("{}\n", container_stacks);
+    }
+
+    return Ok(());
 }
 
 /*--------------------------------------------------------- End of main.rs - */
\ No newline at end of file
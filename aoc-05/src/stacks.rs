@@ -0,0 +1,164 @@
+// Crate stacks numbered from 1, as in the puzzle. Each stack is held bottom
+// first, so its last element is the crate on top.
+
+use std::fmt;
+
+#[derive(Clone, Default)]
+pub struct Stacks {
+    stacks: Vec<Vec<char>>
+}
+impl Stacks {
+    pub fn new(count: usize) -> Stacks {
+        Stacks { stacks: vec![vec![]; count] }
+    }
+
+    pub fn len(&self) -> usize {
+        return self.stacks.len();
+    }
+
+    pub fn height(&self, stack: usize) -> usize {
+        return self.stacks[stack-1].len();
+    }
+
+    pub fn top(&self, stack: usize) -> Option<char> {
+        return self.stacks[stack-1].last().copied();
+    }
+
+    // Removes the top count crates, returned bottom first
+    pub fn take(&mut self, stack: usize, count: usize) -> Vec<char> {
+        let from = &mut self.stacks[stack-1];
+        return from.split_off(from.len() - count);
+    }
+
+    // Adds crates (bottom first) to the top of a stack
+    pub fn put(&mut self, stack: usize, items: Vec<char>) {
+        self.stacks[stack-1].extend(items);
+    }
+
+    // One character per stack; empty stacks are skipped
+    pub fn get_tops(&self) -> String {
+        return (1..=self.len()).filter_map(|s| self.top(s)).collect();
+    }
+}
+// Draws the stacks as the puzzle input does, trailing spaces included, e.g.
+//     [D]    
+// [N] [C]    
+// [Z] [M] [P]
+//  1   2   3 
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let labels: Vec<String> = (1..=self.len()).map(|s| s.to_string()).collect();
+        let widths: Vec<usize> = labels.iter().map(|l| l.len().max(3)).collect();
+        let tallest = self.stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+
+        for level in (0..tallest).rev() {
+            let cells: Vec<String> = self.stacks.iter().zip(widths.iter())
+                .map(|(stack, width)| match stack.get(level) {
+                    Some(item) => centre(&format!("[{}]", item), *width),
+                    None => " ".repeat(*width),
+                })
+                .collect();
+            writeln!(f, "{}", cells.join(" "))?;
+        }
+
+        let footer: Vec<String> = labels.iter().zip(widths.iter())
+            .map(|(label, width)| centre(label, *width))
+            .collect();
+        write!(f, "{}", footer.join(" "))
+    }
+}
+
+/*----------------------------------------------------------------- centre - */
+
+// Odd padding goes on the right, so single digits sit under the crate names
+
+fn centre(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(text.len());
+    let left = padding / 2;
+    return format!("{}{}{}", " ".repeat(left), text, " ".repeat(padding - left));
+}
+
+/*---------------------------------------------------------- parse_drawing - */
+
+// Reads the stacks from the drawing at the top of the input (line 1 onwards),
+// whose last line labels the stacks 1, 2, 3... Each crate belongs to the
+// stack whose label sits under its name, so any number of stacks and labels
+// of any width work, and trailing spaces don't matter.
+
+pub fn parse_drawing(drawing: &[String]) -> Result<Stacks, String> {
+
+    let (footer, rows) = match drawing.split_last() {
+        Some(split) => split,
+        None => return Err(String::from("No stack drawing found")),
+    };
+    let footer_line = drawing.len();
+
+    // Column span of each stack label
+    let mut labels: Vec<(usize, usize)> = vec![];
+    for (column, token) in get_tokens(footer) {
+        let expected = labels.len() + 1;
+        if token != expected.to_string() {
+            return Err(format!("Line {}: expected stack label {}, got [{}]", footer_line, expected, token));
+        }
+        labels.push((column, column + token.chars().count()));
+    }
+    if labels.is_empty() {
+        return Err(format!("Line {}: no stack labels found", footer_line));
+    }
+
+    // Bottom row first, so crates are pushed in stack order
+    let mut stacks = Stacks::new(labels.len());
+    for (index, row) in rows.iter().enumerate().rev() {
+        let line_number = index + 1;
+        let level = rows.len() - 1 - index;
+        let mut filled: Vec<bool> = vec![false; labels.len()];
+
+        for (column, token) in get_tokens(row) {
+            let name: Vec<char> = token.chars().collect();
+            if name.len() != 3 || name[0] != '[' || name[2] != ']' {
+                return Err(format!("Line {}: expected a crate like [A] at column {}, got [{}]",
+                    line_number, column + 1, token));
+            }
+
+            let stack = match labels.iter().position(|(start, end)| *start <= column + 1 && column + 1 < *end) {
+                Some(label) => label + 1,
+                None => return Err(format!("Line {}: crate {} at column {} isn't above a stack label",
+                    line_number, token, column + 1)),
+            };
+            if filled[stack - 1] {
+                return Err(format!("Line {}: more than one crate above stack {}", line_number, stack));
+            }
+            if stacks.height(stack) != level {
+                return Err(format!("Line {}: crate {} in stack {} has nothing beneath it",
+                    line_number, token, stack));
+            }
+
+            stacks.put(stack, vec![name[1]]);
+            filled[stack - 1] = true;
+        }
+    }
+
+    return Ok(stacks);
+}
+
+/*------------------------------------------------------------- get_tokens - */
+
+// Whitespace separated tokens, with the (0-based) column each starts in
+
+fn get_tokens(line: &str) -> Vec<(usize, String)> {
+
+    let mut tokens: Vec<(usize, String)> = vec![];
+    for (column, c) in line.chars().enumerate() {
+        if c.is_whitespace() {
+            continue;
+        }
+        match tokens.last_mut() {
+            Some((start, token)) if *start + token.chars().count() == column => token.push(c),
+            _ => tokens.push((column, c.to_string())),
+        }
+    }
+
+    return tokens;
+}
+
+/*------------------------------------------------------- End of stacks.rs - */
\ No newline at end of file
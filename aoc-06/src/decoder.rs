@@ -0,0 +1,86 @@
+use crate::marker::MarkerDetector;
+
+// Splits a datastream the way the communicator does. Anything before the
+// first start-of-packet marker is noise; the packet runs from just after
+// that marker up to the first start-of-message marker. From then on the
+// stream is messages, each running from just after its marker up to the next
+// one, or to the end of the stream. Packet markers are only looked for until
+// the packet starts, as short runs of distinct bytes are everywhere. The
+// message search restarts after each marker, so markers never overlap.
+
+pub const PACKET_MARKER_SIZE: usize = 4;
+pub const MESSAGE_MARKER_SIZE: usize = 14;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SegmentKind {
+    Packet,
+    Message
+}
+
+#[derive(Debug, Clone)]
+pub struct Segment {
+    pub kind: SegmentKind,
+    pub marker: usize, // offset of the marker's first byte
+    pub start: usize,  // offset of the first byte after the marker
+    pub data: Vec<u8>
+}
+
+pub struct Decoder<I: Iterator<Item = u8>> {
+    bytes: I,
+    packet_detector: MarkerDetector,
+    message_detector: MarkerDetector,
+    offset: usize,
+    current: Option<Segment>
+}
+impl<I: Iterator<Item = u8>> Decoder<I> {
+    pub fn new(bytes: I) -> Decoder<I> {
+        Decoder {
+            bytes,
+            packet_detector: MarkerDetector::new(PACKET_MARKER_SIZE),
+            message_detector: MarkerDetector::new(MESSAGE_MARKER_SIZE),
+            offset: 0,
+            current: None
+        }
+    }
+}
+impl<I: Iterator<Item = u8>> Iterator for Decoder<I> {
+    type Item = Segment;
+
+    fn next(&mut self) -> Option<Segment> {
+        for byte in self.bytes.by_ref() {
+            self.offset += 1;
+            if let Some(current) = self.current.as_mut() {
+                current.data.push(byte);
+            }
+
+            // A message marker always completes after the first packet marker
+            if self.message_detector.push(byte).is_some() {
+                self.message_detector.reset();
+                let next = Segment {
+                    kind: SegmentKind::Message,
+                    marker: self.offset - MESSAGE_MARKER_SIZE,
+                    start: self.offset,
+                    data: vec![]
+                };
+
+                // The marker's bytes were read into the segment it ends, though
+                // it may have begun inside the packet's own marker
+                if let Some(mut finished) = self.current.replace(next) {
+                    finished.data.truncate(finished.data.len().saturating_sub(MESSAGE_MARKER_SIZE));
+                    return Some(finished);
+                }
+            } else if self.current.is_none() && self.packet_detector.push(byte).is_some() {
+                self.current = Some(Segment {
+                    kind: SegmentKind::Packet,
+                    marker: self.offset - PACKET_MARKER_SIZE,
+                    start: self.offset,
+                    data: vec![]
+                });
+            }
+        }
+
+        return self.current.take();
+    }
+}
+
+/*------------------------------------------------------ End of decoder.rs - */
\ No newline at end of file
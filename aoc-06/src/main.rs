@@ -1,16 +1,22 @@
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read};
 use std::fs::File;
 
 #[macro_use]
 mod applog;
 mod startup;
+mod marker;
+mod decoder;
+
+use marker::MarkerDetector;
 
 /*------------------------------------------------------------------- main - */
 
 fn main() {
     let reader = startup::get_reader().unwrap();
 
-    if startup::is_part1() {  
+    if startup::is("decode") {
+        decode(reader);
+    } else if startup::is_part1() {  
         part1(reader);
     } else {
         part2(reader);
@@ -22,70 +28,105 @@ fn main() {
 /*------------------------------------------------------------------ part1 - */
 
 fn part1(reader: BufReader<File>) {
+    find_markers(reader, 4);
+}
 
-    // Read the file
-    for line in reader.lines() {
-        let line = line.unwrap();
-
-        let (mut c1, mut c2, mut c3, mut c4): (char, char, char, char) = ('0', '0', '0', '0');
-        let mut marker_index: usize = 0;
+/*------------------------------------------------------------------ part2 - */
 
-        for (i, c) in line.chars().enumerate() {
-            match i%4 {
-                0 => c1 = c,
-                1 => c2 = c,
-                2 => c3 = c,
-                _ => c4 = c,
-            }
-            if i<3 {
-                continue;
-            }
+fn part2(reader: BufReader<File>) {
+    find_markers(reader, 14);
+}
 
-            if c1!=c2 && c1!=c3 && c1 !=c4 && c2!=c3 && c2!=c4 && c3!=c4 {
-                marker_index = i+1;
-                break;
+/*----------------------------------------------------------- find_markers - */
+
+// Each line is a separate datastream, read a byte at a time. -window [N]
+// overrides the marker size, -all reports every marker rather than the first.
+
+fn find_markers(reader: BufReader<File>, default_window: usize) {
+
+    let window: usize = match startup::value("window") {
+        Some(w) => w.parse::<usize>().unwrap_or_else(|_| panic!("Invalid window: [{}]", w)),
+        None => default_window,
+    };
+    let all = startup::is("all");
+
+    let mut detector = MarkerDetector::new(window);
+    let mut markers: Vec<usize> = vec![];
+    let mut length: usize = 0;
+
+    for byte in reader.bytes() {
+        let byte = byte.unwrap();
+        match byte {
+            b'\n' => {
+                report_markers(&markers, all);
+                detector.reset();
+                markers.clear();
+                length = 0;
+            },
+            b'\r' => {},
+            _ => {
+                length += 1;
+                if all || markers.is_empty() {
+                    if let Some(marker) = detector.push(byte) {
+                        markers.push(marker);
+                    }
+                }
             }
         }
-        applog!("Marker index: {}", marker_index);
-    }   
+    }
+
+    // Last line, if it had no newline
+    if length > 0 {
+        report_markers(&markers, all);
+    }
 }
 
-/*------------------------------------------------------------------ part2 - */
+/*--------------------------------------------------------- report_markers - */
 
-fn part2(reader: BufReader<File>) {
+fn report_markers(markers: &Vec<usize>, all: bool) {
+    if markers.is_empty() {
+        applog!("No marker found");
+    } else if all {
+        applog!("Marker indexes ({}): {:?}", markers.len(), markers);
+    } else {
+        applog!("Marker index: {}", markers[0]);
+    }
+}
+
+/*----------------------------------------------------------------- decode - */
 
-    // Read the file
-    for line in reader.lines() {
+// -decode splits each datastream into its packet and messages
+
+fn decode(reader: BufReader<File>) {
+
+    for (index, line) in reader.lines().enumerate() {
         let line = line.unwrap();
+        applog!("Datastream {} ({} bytes)", index + 1, line.len());
 
-        let block_size: usize = 14;
-        let max: usize = line.len()-block_size;
-        let mut marker_index: usize = 0;
+        let segments: Vec<decoder::Segment> = decoder::Decoder::new(line.bytes()).collect();
+        for segment in segments.iter() {
+            report_segment(segment);
+        }
 
-        for i in 0..max {
-            let slice = &line[i..i+block_size];
-        
-            if has_dup(slice)==false {
-                marker_index = i+block_size;
-                break;
+        for kind in [decoder::SegmentKind::Packet, decoder::SegmentKind::Message] {
+            if !segments.iter().any(|s| s.kind == kind) {
+                applog!("  No start-of-{} marker found", format!("{:?}", kind).to_lowercase());
             }
         }
-        applog!("Marker index: {}", marker_index);
     }
 }
 
-/*---------------------------------------------------------------- has_dup - */
+/*--------------------------------------------------------- report_segment - */
 
-fn has_dup(block: &str) -> bool {
-    let max: usize = block.len()-1;
-    for i in 0..max {
-        let c = block.chars().nth(i).unwrap();
-        let slice = &block[i+1..];
-        if slice.contains(c) {
-            return true;
-        }
+fn report_segment(segment: &decoder::Segment) {
+
+    let mut preview = String::from_utf8_lossy(&segment.data).to_string();
+    if preview.chars().count() > 20 {
+        preview = format!("{}...", preview.chars().take(20).collect::<String>());
     }
-    false
+
+    applog!("  {:?} at {} (marker at {}), {} bytes: [{}]",
+        segment.kind, segment.start, segment.marker, segment.data.len(), preview);
 }
 
 /*--------------------------------------------------------- End of main.rs - */
\ No newline at end of file
@@ -0,0 +1,53 @@
+use std::collections::VecDeque;
+
+// Finds markers: runs of window bytes that are all different. Bytes are fed
+// one at a time, keeping a count of each byte value in the window and of how
+// many values appear more than once, so each byte costs O(1) whatever the
+// window size.
+
+pub struct MarkerDetector {
+    window: usize,
+    counts: [usize; 256],
+    repeated: usize,
+    recent: VecDeque<u8>,
+    position: usize
+}
+impl MarkerDetector {
+    pub fn new(window: usize) -> MarkerDetector {
+        if window == 0 {
+            panic!("Marker window must be at least 1");
+        }
+        MarkerDetector { window, counts: [0; 256], repeated: 0, recent: VecDeque::with_capacity(window), position: 0 }
+    }
+
+    // Returns the number of bytes fed so far if they end with a marker
+    pub fn push(&mut self, byte: u8) -> Option<usize> {
+        self.position += 1;
+
+        if self.recent.len() == self.window {
+            let oldest = self.recent.pop_front().unwrap() as usize;
+            self.counts[oldest] -= 1;
+            if self.counts[oldest] == 1 {
+                self.repeated -= 1;
+            }
+        }
+
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] == 2 {
+            self.repeated += 1;
+        }
+        self.recent.push_back(byte);
+
+        if self.recent.len() == self.window && self.repeated == 0 {
+            return Some(self.position);
+        }
+        return None;
+    }
+
+    // Ready for a new stream
+    pub fn reset(&mut self) {
+        *self = MarkerDetector::new(self.window);
+    }
+}
+
+/*------------------------------------------------------- End of marker.rs - */
\ No newline at end of file
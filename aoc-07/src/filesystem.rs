@@ -0,0 +1,118 @@
+use crate::applog;
+
+// An arena-backed directory tree. Nodes refer to each other by index into
+// nodes; the root "/" is always index 0 and is its own parent. Nodes are
+// only ever added after their parent, which compute_sizes relies on.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeKind {
+    Directory,
+    File
+}
+
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub name: String,
+    pub kind: NodeKind,
+    pub size: u64, // for directories, the recursive total once computed
+    pub parent: usize,
+    pub children: Vec<usize>
+}
+
+#[derive(Debug, Clone)]
+pub struct FileSystem {
+    pub nodes: Vec<Node>
+}
+impl Default for FileSystem {
+    fn default () -> FileSystem {
+        let root = Node { name: String::from("/"), kind: NodeKind::Directory, size: 0, parent: 0, children: vec![] };
+        FileSystem { nodes: vec![root] }
+    }
+}
+impl FileSystem {
+    pub const ROOT: usize = 0;
+
+    pub fn add_node(&mut self, parent: usize, name: &str, kind: NodeKind, size: u64) -> usize {
+        let index = self.nodes.len();
+        self.nodes.push(Node { name: String::from(name), kind, size, parent, children: vec![] });
+        self.nodes[parent].children.push(index);
+        return index;
+    }
+
+    pub fn find_child(&self, parent: usize, name: &str, kind: NodeKind) -> Option<usize> {
+        return self.nodes[parent].children.iter()
+            .copied()
+            .find(|c| self.nodes[*c].name == name && self.nodes[*c].kind == kind);
+    }
+
+    pub fn get_path(&self, index: usize) -> String {
+        if index == FileSystem::ROOT {
+            return String::from("/");
+        }
+
+        let mut names: Vec<&str> = vec![];
+        let mut i = index;
+        while i != FileSystem::ROOT {
+            names.push(&self.nodes[i].name);
+            i = self.nodes[i].parent;
+        }
+        names.reverse();
+
+        return format!("/{}", names.join("/"));
+    }
+
+    // Sets every directory's size to the total of everything beneath it
+    pub fn compute_sizes(&mut self) {
+        for node in self.nodes.iter_mut() {
+            if node.kind == NodeKind::Directory {
+                node.size = 0;
+            }
+        }
+
+        // Children always follow their parent, so a reverse pass is bottom-up
+        for i in (1..self.nodes.len()).rev() {
+            let (parent, size) = (self.nodes[i].parent, self.nodes[i].size);
+            self.nodes[parent].size = match self.nodes[parent].size.checked_add(size) {
+                Some(total) => total,
+                None => panic!("Size of {} overflows a u64.", self.get_path(parent)),
+            };
+        }
+    }
+
+    pub fn directories(&self) -> impl Iterator<Item = (usize, &Node)> {
+        return self.nodes.iter().enumerate().filter(|(_, n)| n.kind == NodeKind::Directory);
+    }
+
+    // Listing in the puzzle's own format, children sorted by name
+    pub fn print_tree(&self) {
+        self.print_node(FileSystem::ROOT, 0);
+    }
+
+    fn print_node(&self, index: usize, depth: usize) {
+        let node = &self.nodes[index];
+        let kind = if node.kind == NodeKind::Directory {"dir"} else {"file"};
+        applog!("{}- {} ({}, size={})", "  ".repeat(depth), node.name, kind, node.size);
+
+        let mut children = node.children.clone();
+        children.sort_by(|a, b| self.nodes[*a].name.cmp(&self.nodes[*b].name));
+        for c in children {
+            self.print_node(c, depth + 1);
+        }
+    }
+
+    // One line per directory, largest first, like `du | sort -rn`
+    pub fn print_du(&self) {
+        let mut sizes: Vec<(u64, String)> = self.directories()
+            .map(|(i, d)| (d.size, self.get_path(i)))
+            .collect();
+        sizes.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        let width = sizes.iter().map(|(s, _)| s.to_string().len()).max().unwrap_or(0);
+        for (size, path) in sizes {
+            let size = format!("{:>width$}", size, width = width);
+            applog!("{}  {}", size, path);
+        }
+    }
+}
+
+/*-------------------------------------------------- End of filesystem.rs - */
\ No newline at end of file
@@ -1,21 +1,41 @@
 use std::io::{BufRead, BufReader};
 use std::fs::File;
+use std::collections::HashSet;
 
 #[macro_use]
 mod applog;
 mod startup;
-
-#[derive(Debug, Clone)]
-struct Directory {
-    name: String,
-    depth: i32,
-    subdirs: Vec<String>,
-    local_bytes: i32,
-    subdir_bytes: i32,
+mod filesystem;
+
+use filesystem::{FileSystem, NodeKind};
+
+// State of the terminal session being replayed
+struct Shell {
+    fs: FileSystem,
+    cwd: usize,
+    listed: HashSet<usize>, // directories whose ls output we've already seen
+    listing: bool,          // true from "$ ls" until the next command
+    relisting: bool,        // the current ls is for a directory already listed
+    line_number: usize,
+    warnings: u32
+}
+impl Default for Shell {
+    fn default () -> Shell {
+        Shell {
+            fs: FileSystem::default(),
+            cwd: FileSystem::ROOT,
+            listed: HashSet::new(),
+            listing: false,
+            relisting: false,
+            line_number: 0,
+            warnings: 0
+        }
+    }
 }
-impl Default for Directory {
-    fn default () -> Directory {
-        Directory{name: String::default(), depth: 0, subdirs: vec![], local_bytes: 0, subdir_bytes: 0}
+impl Shell {
+    fn warn(&mut self, message: &str) {
+        self.warnings += 1;
+        applog!("Warning: line {}: {}", self.line_number, message);
     }
 }
 
@@ -24,7 +44,9 @@ impl Default for Directory {
 fn main() {
     let reader = startup::get_reader().unwrap();
 
-    if startup::is_part1() {  
+    if startup::is("tree") || startup::is("du") {
+        report(reader);
+    } else if startup::is_part1() {  
         part1(reader);
     } else {
         part2(reader);
@@ -33,21 +55,35 @@ fn main() {
     applog::end_timestamp(startup::get_start_time());
 }
 
+/*----------------------------------------------------------------- report - */
+
+// -tree prints the whole tree, -du a table of directory sizes
+
+fn report(reader: BufReader<File>) {
+
+    let fs = read_shell_history(reader);
+
+    if startup::is("tree") {
+        fs.print_tree();
+    }
+    if startup::is("du") {
+        fs.print_du();
+    }
+}
+
 /*------------------------------------------------------------------ part1 - */
 
 fn part1(reader: BufReader<File>) {
 
-    let mut directories: Vec<Directory> = vec![];
-    read_shell_history(reader, &mut directories);
+    let fs = read_shell_history(reader);
 
     // Finally get total of directories with at most 100000 bytes
-    let mut total_100k: i32 = 0;
-    applog!("Examining: {} directories...", directories.len());
-    for d in directories.iter() {
-        applog!("{:?}", d);
-        let total_bytes = d.local_bytes+d.subdir_bytes;
-        if total_bytes <= 100000 {
-            total_100k += total_bytes;
+    let mut total_100k: u64 = 0;
+    applog!("Examining: {} directories...", fs.directories().count());
+    for (i, d) in fs.directories() {
+        applog!("{}: {} bytes", fs.get_path(i), d.size);
+        if d.size <= 100000 {
+            total_100k += d.size;
         }
     }
 
@@ -56,168 +92,178 @@ fn part1(reader: BufReader<File>) {
 
 /*------------------------------------------------------------------ part2 - */
 
+// Disk and required space default to the puzzle's, overridden by
+// -disk-size and -required-space
+
 fn part2(reader: BufReader<File>) {
 
-    let mut directories: Vec<Directory> = vec![];
-    read_shell_history(reader, &mut directories);
+    let fs = read_shell_history(reader);
 
-    let total_space: i32 = 70000000;
-    let required_unused: i32 = 30000000;
-    let total_used = directories[0].local_bytes + directories[0].subdir_bytes;
-    let free_space = total_space - total_used;
+    let total_space: u64 = get_size_option("disk-size", 70000000);
+    let required_unused: u64 = get_size_option("required-space", 30000000);
+    let total_used = fs.nodes[FileSystem::ROOT].size;
+
+    if total_used > total_space {
+        applog!("Warning: {} bytes used is more than the {} byte disk.", total_used, total_space);
+    }
+    let free_space = total_space.saturating_sub(total_used);
 
     if free_space >= required_unused {
         applog!("We have enough space! (With {} bytes to spare).", free_space - required_unused);
-    } else {
-        let bytes_required = required_unused - free_space;
-        applog!("We don't have enough space! (We need {} more bytes).", bytes_required);
-
-        // Find smallest directory that's big enough
-        let mut best_folder_index=0;
-        let mut best_bytes_so_far = total_used;
-        for (i, d) in directories.iter().enumerate() {
-            let dir_bytes = d.local_bytes + d.subdir_bytes;
-            if dir_bytes >= bytes_required && dir_bytes < best_bytes_so_far {
-                // We have a new leader
-                best_folder_index = i;
-                best_bytes_so_far = dir_bytes;
-            }
-        }
+        return;
+    }
+
+    let bytes_required = required_unused - free_space;
+    applog!("We don't have enough space! (We need {} more bytes).", bytes_required);
 
-        applog!("Folder index {} will do the job: {} bytes.", best_folder_index, best_bytes_so_far);
+    match find_directory_to_delete(&fs, bytes_required) {
+        Some(path) => applog!("PART2: Deleting {} will do the job.", path),
+        None => applog!("PART2: No directory is big enough - even / only has {} bytes.", total_used),
     }
 }
 
-/*----------------------------------------------------- read_shell_history - */
+/*----------------------------------------------- find_directory_to_delete - */
 
-fn read_shell_history(reader: BufReader<File>, directories: &mut Vec<Directory>) {
+// Returns the path of the smallest directory holding at least bytes_required
 
-    //let mut directories: Vec<Directory> = vec![];
-    let mut index: usize = 0;
+fn find_directory_to_delete(fs: &FileSystem, bytes_required: u64) -> Option<String> {
 
-    for line in reader.lines() {
-        let line = line.unwrap();
+    let (index, dir) = fs.directories()
+        .filter(|(_, d)| d.size >= bytes_required)
+        .min_by_key(|(_, d)| d.size)?;
 
-        // New directory
-        if line.starts_with("$ cd ") {
-            let name = &line[5..];
-            change_directory(name, directories, &mut index);
-            continue;
-        } else if line == "$ ls" {
-            continue; // skip
-        }
+    applog!("{} is the smallest big enough: {} bytes.", fs.get_path(index), dir.size);
+    return Some(fs.get_path(index));
+}
 
-        let mut dir: &mut Directory = &mut directories[index];
-        let chunks: Vec<&str> = line.split(" ").collect();
-                       
-        if line.starts_with("dir ") {
-            let subdir: &str = chunks[1];
-            dir.subdirs.push(subdir.to_string());
-        } else {
-            let file_size: i32 = chunks[0].parse::<i32>().unwrap();
-            dir.local_bytes += file_size;
-        }
-    }
+/*-------------------------------------------------------- get_size_option - */
 
-    add_subdir_bytes(directories);
+fn get_size_option(name: &str, default: u64) -> u64 {
+    return match startup::value(name) {
+        Some(v) => v.parse::<u64>().unwrap_or_else(|_| panic!("Invalid -{}: [{}]", name, v)),
+        None => default,
+    };
 }
 
-/*------------------------------------------------------- change_directory - */
+/*----------------------------------------------------- read_shell_history - */
 
-fn change_directory(name: &str, directories: &mut Vec<Directory>, index: &mut usize) {
+// Repeated cds and listings resolve to the same nodes, and anything
+// inconsistent is reported as a warning rather than trusted blindly.
 
-    let mut add_dir: bool = false;
+fn read_shell_history(reader: BufReader<File>) -> FileSystem {
+
+    let mut shell = Shell::default();
+
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.unwrap();
+        shell.line_number = i + 1;
 
-    if name == "/" {
-        *index = 0; // back to root
-    } else if name == ".." {
-        if *index>0 {
-            *index = get_parent_index(&directories[*index].name, directories);
+        if let Some(path) = line.strip_prefix("$ cd ") {
+            shell.listing = false;
+            change_directory(path, &mut shell);
+        } else if line == "$ ls" {
+            shell.listing = true;
+            shell.relisting = !shell.listed.insert(shell.cwd);
+        } else if line.starts_with("$ ") {
+            shell.listing = false;
+            shell.warn(&format!("Unsupported command [{}], ignored", line));
+        } else {
+            read_listing_entry(&line, &mut shell);
         }
-    } else {
-        add_dir = true;
     }
 
-    if directories.len()==0 {
-        add_dir = true;
+    if shell.warnings > 0 {
+        applog!("{} warning(s) while reading shell history.", shell.warnings);
     }
 
-    if add_dir {
-        add_directory(name, directories, index);
-    }
+    shell.fs.compute_sizes();
+    return shell.fs;
 }
 
-/*---------------------------------------------------------- add_directory - */
+/*------------------------------------------------------- change_directory - */
 
-fn add_directory(name: &str, directories: &mut Vec<Directory>, index: &mut usize) {
-    let mut dir = Directory::default();
-    // Non-root directories must be prefixed with parent
-    if directories.len()==0 {
-        dir.name = String::from(name);
-    } else {
-        let parent = &directories[*index];
+// Accepts absolute paths and multiple segments, e.g. "/a/e" or "../d"
 
-        if parent.name == "/" {
-            dir.name = format!("/{}", name);
-        } else {
-            dir.name = format!("{}/{}", parent.name, name);
+fn change_directory(path: &str, shell: &mut Shell) {
+
+    if path.starts_with('/') {
+        shell.cwd = FileSystem::ROOT; // back to root
+    }
+
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        match segment {
+            "." => (),
+            ".." => {
+                if shell.cwd == FileSystem::ROOT {
+                    shell.warn("cd .. from /, staying at /");
+                } else {
+                    shell.cwd = shell.fs.nodes[shell.cwd].parent;
+                }
+            },
+            name => {
+                shell.cwd = match shell.fs.find_child(shell.cwd, name, NodeKind::Directory) {
+                    Some(child) => child,
+                    None => {
+                        let parent = shell.fs.get_path(shell.cwd);
+                        shell.warn(&format!("cd into unlisted directory [{}] in [{}]", name, parent));
+                        shell.fs.add_node(shell.cwd, name, NodeKind::Directory, 0)
+                    }
+                };
+            },
         }
-        dir.depth = parent.depth + 1;
     }
-    
-    directories.push(dir);
-    *index = directories.len() - 1;
 }
 
-/*--------------------------------------------------------- get_parent_len - */
+/*----------------------------------------------------- read_listing_entry - */
 
-// NB: parent of "/" is ""!
+// One line of ls output: "dir [name]" or "[size] [name]"
 
-fn get_parent_len(name: &String, len: usize) -> usize {
-    let current_dir: &str = &name[..len];
-    let mut parent_len: usize = current_dir.rfind('/').unwrap();
-    if parent_len == 0 {
-        if current_dir.len()==1 {
-            parent_len = 0;
-        } else {
-            parent_len = 1;
-        }
+fn read_listing_entry(line: &str, shell: &mut Shell) {
+
+    if !shell.listing {
+        shell.warn(&format!("Output [{}] without a preceding ls, treating as a listing", line));
+        shell.listing = true;
     }
-    return parent_len;
-}
 
-/*------------------------------------------------------- get_folder_index - */
+    let chunks: Vec<&str> = line.splitn(2, ' ').collect();
+    if chunks.len() != 2 {
+        shell.warn(&format!("Unrecognised listing entry [{}], ignored", line));
+        return;
+    }
+    let (first, name) = (chunks[0], chunks[1]);
+    let path = shell.fs.get_path(shell.cwd);
 
-fn get_folder_index(name: &str, directories: &Vec<Directory>) -> usize {
-    let mut index = 0;
-    for (i, d) in directories.iter().enumerate() {
-        if d.name == name {
-            index = i;
-            break;
+    if first == "dir" {
+        if shell.fs.find_child(shell.cwd, name, NodeKind::Directory).is_none() {
+            if shell.relisting {
+                shell.warn(&format!("Directory [{}] in [{}] missing from earlier listing", name, path));
+            }
+            shell.fs.add_node(shell.cwd, name, NodeKind::Directory, 0);
         }
+        return;
     }
-    
-    return index;
-}
 
-fn get_parent_index(name: &String, directories: &Vec<Directory>) -> usize {
-    let parent_len = get_parent_len(name, name.len());
-    return get_folder_index(&name[..parent_len], directories);
-}
-/*------------------------------------------------------- add_subdir_bytes - */
-
-fn add_subdir_bytes(directories: &mut Vec<Directory>) {
-
-    let mut i=0;
-    while i<directories.len() {
-        let mut parent_len: usize = directories[i].name.len();
-        parent_len = get_parent_len(&directories[i].name, parent_len);
-        while parent_len>0 {
-            let parent_index = get_folder_index(&directories[i].name[..parent_len], directories);
-            directories[parent_index].subdir_bytes += directories[i].local_bytes + directories[i].subdir_bytes;
-            parent_len = get_parent_len(&directories[i].name, parent_len);
+    let file_size: u64 = match first.parse::<u64>() {
+        Ok(size) => size,
+        Err(_) => {
+            shell.warn(&format!("Invalid file size in [{}], ignored", line));
+            return;
         }
-        i += 1;
+    };
+
+    match shell.fs.find_child(shell.cwd, name, NodeKind::File) {
+        Some(file) if shell.fs.nodes[file].size != file_size => {
+            shell.warn(&format!("File [{}] in [{}] listed as {} bytes, previously {}, using the latest",
+                name, path, file_size, shell.fs.nodes[file].size));
+            shell.fs.nodes[file].size = file_size;
+        },
+        Some(_) => (), // already known
+        None => {
+            if shell.relisting {
+                shell.warn(&format!("File [{}] in [{}] missing from earlier listing", name, path));
+            }
+            shell.fs.add_node(shell.cwd, name, NodeKind::File, file_size);
+        },
     }
 }
 
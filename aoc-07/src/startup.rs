@@ -47,6 +47,23 @@ pub fn is_part1() -> bool {
     }
 }
 
+/*--------------------------------------------------------------------- is - */
+
+pub fn is(name: &str) -> bool {
+    let args: Vec<String> = env::args().collect();
+    return args.contains(&format!("-{}", name));
+}
+
+/*------------------------------------------------------------------ value - */
+
+// Returns the argument following -[name], e.g. "-disk-size 70000000"
+
+pub fn value(name: &str) -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|a| *a == format!("-{}", name))?;
+    return args.get(index+1).cloned();
+}
+
 /*---------------------------------------------------------- get_start_time - */
 
 pub fn get_start_time() -> Instant {
@@ -4,13 +4,23 @@ use std::fs::File;
 #[macro_use]
 mod applog;
 mod startup;
+mod render;
+
+// Results for every tree in the grid, indexed [row][column]
+#[derive(Debug, Clone)]
+struct Forest {
+    visible: Vec<Vec<bool>>,
+    scenic_values: Vec<Vec<u64>>
+}
 
 /*------------------------------------------------------------------- main - */
 
 fn main() {
     let reader = startup::get_reader().unwrap();
 
-    if startup::is_part1() {  
+    if startup::is("render") || startup::value("heatmap").is_some() {
+        render(reader);
+    } else if startup::is_part1() {  
         part1(reader);
     } else {
         part2(reader);
@@ -19,6 +29,30 @@ fn main() {
     applog::end_timestamp(startup::get_start_time());
 }
 
+/*----------------------------------------------------------------- render - */
+
+// -render draws the forest, -heatmap [file] writes the scenic values
+
+fn render(reader: BufReader<File>) {
+
+    let heatmap_file = startup::value("heatmap");
+    if let Some(Err(e)) = heatmap_file.as_deref().map(render::check_heatmap_filename) {
+        panic!("{}", e);
+    }
+
+    let mut grid: Vec<Vec<u32>> = vec![];
+    read_grid(reader, &mut grid);
+
+    let forest = analyse_forest(&grid);
+
+    if startup::is("render") {
+        render::draw_forest(&grid, &forest);
+    }
+    if let Some(filename) = heatmap_file {
+        render::write_heatmap(&forest, &filename);
+    }
+}
+
 /*------------------------------------------------------------------ part1 - */
 
 fn part1(reader: BufReader<File>) {
@@ -26,18 +60,8 @@ fn part1(reader: BufReader<File>) {
     let mut grid: Vec<Vec<u32>> = vec![];
     read_grid(reader, &mut grid);
 
-    let row_size: usize = grid.len();
-    let col_size: usize = grid[0].len();
-    
-    let mut tall_trees: i32 = 0;
-    for r in 0..row_size {
-        for c in 0..col_size {
-            if is_bigger_than_neighbours(r, c, &grid) {
-                //applog!("Cell ({},{}) bigger than neighbours.", r, c);
-                tall_trees += 1;
-            }
-        }
-    }
+    let forest = analyse_forest(&grid);
+    let tall_trees: usize = forest.visible.iter().flatten().filter(|v| **v).count();
 
     applog!("Number of tall trees: {}", tall_trees);
 }
@@ -49,13 +73,11 @@ fn part2(reader: BufReader<File>) {
     let mut grid: Vec<Vec<u32>> = vec![];
     read_grid(reader, &mut grid);
 
-    let row_size: usize = grid.len();
-    let col_size: usize = grid[0].len();
-    
-    let mut max_scenic_value: i32 = 0;
-    for r in 0..row_size {
-        for c in 0..col_size {
-            let scenic_value = compute_scenic_value(r, c, &grid);
+    let forest = analyse_forest(&grid);
+
+    let mut max_scenic_value: u64 = 0;
+    for (r, row) in forest.scenic_values.iter().enumerate() {
+        for (c, scenic_value) in row.iter().copied().enumerate() {
             if scenic_value > max_scenic_value {
                 applog!("Cell ({},{}) is new max scenic value: {}.", r, c, scenic_value);
                 max_scenic_value = scenic_value;
@@ -84,159 +106,76 @@ fn read_grid(reader: BufReader<File>, grid: &mut Vec<Vec<u32>>) {
     //applog!("Grid: {:?}", grid);
 }
 
-/*---------------------------------------------- is_bigger_than_neighbours - */
+/*--------------------------------------------------------- analyse_forest - */
 
-fn is_bigger_than_neighbours(row: usize, column: usize, grid: &Vec<Vec<u32>>) -> bool {
-
-    let max_row: usize = grid.len()-1;
-    let max_col: usize = grid[0].len()-1; 
-
-    // First check if perimeter tree
-    if row==0 || row==max_row {
-        return true;
-    } else if column==0 || column==max_col {
-        return true;
-    }
+// Computes both parts for every tree at once: each row and column is swept
+// in both directions, so every tree is visited four times in total.
 
-    // Inner trees get here
-    let h = grid[row][column]; // height of our tree
+fn analyse_forest(grid: &Vec<Vec<u32>>) -> Forest {
 
-    // 1. Check trees above: keep c static and reduce down r to zero
-    let mut r = row-1;
-    let mut c = column;
-    loop {
-        if grid[r][c] >= h {
-            break;
-        }
-        if r == 0 {
-            return true; // got to edge of grid and still the tallest
-        }
-        r -= 1;
-    }
+    let row_size: usize = grid.len();
+    let col_size: usize = grid[0].len();
 
-    // 2. Check trees below: keep c static and increase r to max
-    r = row+1;
-    c = column;
-    loop {
-        if grid[r][c] >= h {
-            break;
-        }
-        if r == max_row {
-            return true; // got to edge of grid and still the tallest
-        }
-        r += 1;
-    }
+    let mut forest = Forest {
+        visible: vec![vec![false; col_size]; row_size],
+        scenic_values: vec![vec![1; col_size]; row_size]
+    };
 
-    // 3. Check trees to the left: keep r static and reduce c to zero
-    r = row;
-    c = column-1;
-    loop {
-        if grid[r][c] >= h {
-            break;
-        }
-        if c == 0 {
-            return true; // got to edge of grid and still the tallest
-        }
-        c -= 1;
+    for r in 0..row_size {
+        let mut line: Vec<(usize, usize)> = (0..col_size).map(|c| (r, c)).collect();
+        sweep_line(&line, grid, &mut forest);
+        line.reverse();
+        sweep_line(&line, grid, &mut forest);
     }
 
-    // 4. Check trees to the right: keep r static and increase c to max
-    r = row;
-    c = column+1;
-    loop {
-        if grid[r][c] >= h {
-            break;
-        }
-        if c == max_col {
-            return true; // got to edge of grid and still the tallest
-        }
-        c += 1;
+    for c in 0..col_size {
+        let mut line: Vec<(usize, usize)> = (0..row_size).map(|r| (r, c)).collect();
+        sweep_line(&line, grid, &mut forest);
+        line.reverse();
+        sweep_line(&line, grid, &mut forest);
     }
 
-    return false; // No line of sight out of the grid
+    return forest;
 }
 
-/*---------------------------------------------- compute_scenic_value - */
-
-fn compute_scenic_value(row: usize, column: usize, grid: &Vec<Vec<u32>>) -> i32 {
+/*------------------------------------------------------------- sweep_line - */
 
-    let max_row: usize = grid.len()-1;
-    let max_col: usize = grid[0].len()-1; 
+// Walks a line of trees away from one edge, looking back towards it.
+// A tree is visible from that edge if it beats the running maximum so far.
+// The stack holds the trees that could still block the view of a later
+// tree: each shorter than the one beneath it, so anything below the new
+// tree's height can be popped for good. Whatever is left on top blocks it.
 
-    // Edge trees have no scenic value (as one of factors is zero)
-    if row==0 || row==max_row || column==0 || column==max_col {
-        return 0;
-    }
+fn sweep_line(line: &Vec<(usize, usize)>, grid: &Vec<Vec<u32>>, forest: &mut Forest) {
 
-    let h = grid[row][column]; // height of our tree
+    let mut tallest: Option<u32> = None;
+    let mut blockers: Vec<usize> = vec![]; // positions along the line
 
-    let mut scenic_value: i32 = 1;
+    for (i, (r, c)) in line.iter().copied().enumerate() {
+        let h = grid[r][c]; // height of our tree
 
-    // 1. Check trees above: keep c static and reduce down r to zero
-    let mut r = row;
-    let mut c = column;
-    let mut factor: i32 = 0;
-    loop {
-        r -= 1;
-        factor += 1;
-        if grid[r][c] >= h {
-            break;
+        if tallest.map_or(true, |t| h > t) {
+            forest.visible[r][c] = true;
+            tallest = Some(h);
         }
-        if r == 0 {
-            break;
-        }
-    }
-    scenic_value *= factor;
-
-    // 2. Check trees below: keep c static and increase r to max
-    r = row;
-    c = column;
-    factor = 0;
-    loop {
-        r += 1;
-        factor += 1;
-        if grid[r][c] >= h {
-            break;
-        }
-        if r == max_row {
-            break;
-        }
-    }
-    scenic_value *= factor;
-
-    // 3. Check trees to the left: keep r static and reduce c to zero
-    r = row;
-    c = column;
-    factor = 0;
-    loop {
-        c -= 1;
-        factor += 1;
-        if grid[r][c] >= h {
-            break;
-        }
-        if c == 0 {
-            break;
-        }
-    }
-    scenic_value *= factor;
-
-    // 4. Check trees to the right: keep r static and increase c to max
-    r = row;
-    c = column;
-    factor = 0;
-    loop {
-        c += 1;
-        factor += 1;
-        if grid[r][c] >= h {
-            break;
-        }
-        if c == max_col {
-            break;
+
+        while let Some(b) = blockers.last() {
+            let (br, bc) = line[*b];
+            if grid[br][bc] >= h {
+                break;
+            }
+            blockers.pop();
         }
-    }
-    scenic_value *= factor;
 
-    return scenic_value;
+        // Trees on the edge see 0 trees in this direction
+        let distance = match blockers.last() {
+            Some(b) => i - b,
+            None => i,
+        };
+        forest.scenic_values[r][c] *= distance as u64;
+
+        blockers.push(i);
+    }
 }
 
 /*--------------------------------------------------------- End of main.rs - */
\ No newline at end of file
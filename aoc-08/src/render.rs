@@ -0,0 +1,83 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use ansi_term::Colour;
+
+use crate::applog;
+use crate::startup;
+use crate::Forest;
+
+/*------------------------------------------------------------ draw_forest - */
+
+// Draws the grid with visible trees highlighted. With -mono, hidden trees
+// are drawn as '.' instead of being dimmed.
+
+pub fn draw_forest(grid: &Vec<Vec<u32>>, forest: &Forest) {
+
+    let mono = startup::is("mono");
+
+    for (r, row) in grid.iter().enumerate() {
+        let mut line: String = String::new();
+        for (c, height) in row.iter().enumerate() {
+            let tree = height.to_string();
+            let cell = match (forest.visible[r][c], mono) {
+                (true, true) => tree,
+                (false, true) => String::from("."),
+                (true, false) => Colour::Green.bold().paint(tree).to_string(),
+                (false, false) => Colour::Fixed(240).paint(tree).to_string(),
+            };
+            line.push_str(&cell);
+        }
+        println!("{}", line);
+    }
+}
+
+/*------------------------------------------------- check_heatmap_filename - */
+
+// Format follows the file's extension: .csv or .pgm
+
+pub fn check_heatmap_filename(filename: &str) -> Result<(), String> {
+    if filename.ends_with(".csv") || filename.ends_with(".pgm") {
+        return Ok(());
+    }
+    return Err(format!("Unsupported heatmap format: [{}], expected .csv or .pgm", filename));
+}
+
+/*---------------------------------------------------------- write_heatmap - */
+
+// .csv holds the raw scenic values, .pgm a greyscale image on a log scale
+// (brighter is more scenic), as the best few trees would otherwise leave the
+// rest black.
+
+pub fn write_heatmap(forest: &Forest, filename: &str) {
+
+    if let Err(e) = check_heatmap_filename(filename) {
+        panic!("{}", e);
+    }
+
+    let mut writer = BufWriter::new(File::create(filename).unwrap());
+
+    if filename.ends_with(".csv") {
+        for row in forest.scenic_values.iter() {
+            let values: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+            writeln!(writer, "{}", values.join(",")).unwrap();
+        }
+    } else {
+        let height = forest.scenic_values.len();
+        let width = forest.scenic_values[0].len();
+        let max = forest.scenic_values.iter().flatten().copied().max().unwrap_or(0);
+        let scale = ((max + 1) as f64).ln();
+
+        write!(writer, "P5\n{} {}\n255\n", width, height).unwrap();
+        for row in forest.scenic_values.iter() {
+            let pixels: Vec<u8> = row.iter()
+                .map(|v| if max == 0 {0} else {(255.0 * ((v + 1) as f64).ln() / scale).round() as u8})
+                .collect();
+            writer.write_all(&pixels).unwrap();
+        }
+    }
+
+    writer.flush().unwrap();
+    applog!("Scenic heatmap written to: {}", filename);
+}
+
+/*------------------------------------------------------- End of render.rs - */
\ No newline at end of file
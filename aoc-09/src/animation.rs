@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::startup;
+use crate::Position;
+
+// Draws the rope the way the puzzle statement does: H for the head, then
+// numbered knots (T for the tail of a two-knot rope), s for the start and,
+// unless -hide-visited, # for cells the tail has visited. Where knots overlap
+// the one nearest the head is shown. Rows are drawn with the highest y first.
+//
+// Options: -animate enables it; -every-step draws after each step instead of
+// each instruction; -viewport x0,y0,x1,y1 picks the area (default: everywhere
+// the head goes); -frames [file] writes frames to a file instead of the
+// terminal, where -delay [ms] pauses between them.
+
+pub struct Animator {
+    top_left: Position,
+    bottom_right: Position,
+    every_step: bool,
+    hide_visited: bool,
+    delay: Option<Duration>,
+    output: Option<BufWriter<File>>
+}
+impl Animator {
+    // Returns None unless -animate was given
+    pub fn from_options(moves: &Vec<String>) -> Option<Animator> {
+        if !startup::is("animate") {
+            return None;
+        }
+
+        let (top_left, bottom_right) = match startup::value("viewport") {
+            Some(viewport) => parse_viewport(&viewport),
+            None => get_head_bounds(moves),
+        };
+
+        let delay = startup::value("delay")
+            .map(|d| Duration::from_millis(d.parse::<u64>().unwrap_or_else(|_| panic!("Invalid delay: [{}]", d))));
+
+        let output = startup::value("frames")
+            .map(|filename| BufWriter::new(File::create(filename).unwrap()));
+
+        return Some(Animator {
+            top_left,
+            bottom_right,
+            every_step: startup::is("every-step"),
+            hide_visited: startup::is("hide-visited"),
+            delay,
+            output
+        });
+    }
+
+    pub fn initial_state(&mut self, rope: &Vec<Position>, tail_visited: &HashSet<Position>) {
+        self.draw_frame("Initial State", rope, tail_visited);
+    }
+
+    pub fn after_step(&mut self, line: &str, rope: &Vec<Position>, tail_visited: &HashSet<Position>) {
+        if self.every_step {
+            self.draw_frame(line, rope, tail_visited);
+        }
+    }
+
+    pub fn after_move(&mut self, line: &str, rope: &Vec<Position>, tail_visited: &HashSet<Position>) {
+        if !self.every_step {
+            self.draw_frame(line, rope, tail_visited);
+        }
+    }
+
+    pub fn finish(&mut self) {
+        if let Some(output) = self.output.as_mut() {
+            output.flush().unwrap();
+        }
+    }
+
+    fn draw_frame(&mut self, title: &str, rope: &Vec<Position>, tail_visited: &HashSet<Position>) {
+        let mut frame: String = format!("== {} ==\n\n", title);
+
+        for y in (self.top_left.y..=self.bottom_right.y).rev() {
+            for x in self.top_left.x..=self.bottom_right.x {
+                frame.push(self.get_cell_char(Position { x, y }, rope, tail_visited));
+            }
+            frame.push('\n');
+        }
+
+        match self.output.as_mut() {
+            Some(output) => writeln!(output, "{}", frame).unwrap(),
+            None => {
+                if let Some(delay) = self.delay {
+                    // Clear the terminal so frames replace each other
+                    print!("\x1b[2J\x1b[H");
+                    println!("{}", frame);
+                    thread::sleep(delay);
+                } else {
+                    println!("{}", frame);
+                }
+            }
+        }
+    }
+
+    fn get_cell_char(&self, cell: Position, rope: &Vec<Position>, tail_visited: &HashSet<Position>) -> char {
+
+        // rope is stored tail first, so search from the head back
+        let knots = rope.len();
+        for (knot, position) in rope.iter().rev().enumerate() {
+            if *position == cell {
+                return get_knot_label(knot, knots);
+            }
+        }
+
+        if cell == Position::default() {
+            return 's';
+        }
+        if !self.hide_visited && tail_visited.contains(&cell) {
+            return '#';
+        }
+        return '.';
+    }
+}
+
+/*--------------------------------------------------------- get_knot_label - */
+
+// Knots are numbered from the head (0)
+
+fn get_knot_label(knot: usize, knots: usize) -> char {
+    if knot == 0 {
+        return 'H';
+    }
+    if knots == 2 {
+        return 'T';
+    }
+    return std::char::from_digit((knot % 36) as u32, 36).unwrap();
+}
+
+/*--------------------------------------------------------- parse_viewport - */
+
+// Assumes format: x0,y0,x1,y1
+
+fn parse_viewport(viewport: &str) -> (Position, Position) {
+
+    let values: Vec<i32> = viewport.split(',')
+        .map(|v| v.trim().parse::<i32>()
+            .unwrap_or_else(|_| panic!("Invalid viewport value [{}] in [{}]", v, viewport)))
+        .collect();
+
+    if values.len() != 4 {
+        panic!("Expected viewport as x0,y0,x1,y1, got: [{}]", viewport);
+    }
+
+    let top_left = Position { x: values[0].min(values[2]), y: values[1].min(values[3]) };
+    let bottom_right = Position { x: values[0].max(values[2]), y: values[1].max(values[3]) };
+
+    return (top_left, bottom_right);
+}
+
+/*-------------------------------------------------------- get_head_bounds - */
+
+// The rest of the rope always stays within the area the head covers
+
+fn get_head_bounds(moves: &Vec<String>) -> (Position, Position) {
+
+    let mut head = Position::default();
+    let mut top_left = Position::default();
+    let mut bottom_right = Position::default();
+
+    for line in moves {
+        let tokens: Vec<&str> = line.split(' ').collect();
+        let steps: i32 = tokens[1].parse::<i32>().unwrap();
+        match tokens[0] {
+            "R" => head.x += steps,
+            "L" => head.x -= steps,
+            "U" => head.y += steps,
+            _ => head.y -= steps,
+        }
+
+        top_left.x = top_left.x.min(head.x);
+        top_left.y = top_left.y.min(head.y);
+        bottom_right.x = bottom_right.x.max(head.x);
+        bottom_right.y = bottom_right.y.max(head.y);
+    }
+
+    return (top_left, bottom_right);
+}
+
+/*---------------------------------------------------- End of animation.rs - */
\ No newline at end of file
@@ -1,11 +1,15 @@
 use std::io::{BufRead, BufReader};
 use std::fs::File;
+use std::collections::HashSet;
 
 #[macro_use]
 mod applog;
 mod startup;
+mod animation;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+use animation::Animator;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 struct Position {
     x: i32,
     y: i32,
@@ -21,11 +25,16 @@ impl Default for Position {
 fn main() {
     let reader = startup::get_reader().unwrap();
 
-    if startup::is_part1() {  
-        simulate_rope_movement(reader, 2);
-    } else {
-        simulate_rope_movement(reader, 10);
-    }  
+    let default_knots: usize = if startup::is_part1() {2} else {10};
+    let number_of_knots: usize = match startup::value("knots") {
+        Some(k) => k.parse::<usize>().unwrap_or_else(|_| panic!("Invalid knot count: [{}]", k)),
+        None => default_knots,
+    };
+    if number_of_knots == 0 {
+        panic!("A rope needs at least 1 knot.");
+    }
+
+    simulate_rope_movement(reader, number_of_knots);
 
     applog::end_timestamp(startup::get_start_time());
 }
@@ -35,30 +44,60 @@ fn main() {
 fn simulate_rope_movement(reader: BufReader<File>, number_of_knots: usize) {
     let mut rope: Vec<Position> = vec![Position::default(); number_of_knots];
 
-    let mut tail_positions: Vec<Position> = vec![];
-    tail_positions.push(rope[0]); // always at least 1 tail position - the start
+    // Positions visited by each knot, indexed as rope is (tail first)
+    let mut visited: Vec<HashSet<Position>> = vec![HashSet::new(); number_of_knots];
+    record_positions(&rope, &mut visited); // always at least 1 position - the start
+
+    // Read the file and execute moves, keeping track of new positions
+    let moves: Vec<String> = reader.lines().map(|line| line.unwrap()).collect();
+
+    let mut animator = Animator::from_options(&moves);
+    if let Some(a) = animator.as_mut() {
+        a.initial_state(&rope, &visited[0]);
+    }
+
+    for line in moves.iter() {
+        execute_move(line, &mut rope, &mut visited, &mut animator);
+    }
 
-    // Read the file and execute moves, keeping track of new tail positions
-    for line in reader.lines() {
-        let line = line.unwrap();
-        execute_move(&line, &mut rope, &mut tail_positions);
+    if let Some(a) = animator.as_mut() {
+        a.finish();
+    }
+
+    // -all-knots reports every knot, numbered from the head as in the puzzle
+    if startup::is("all-knots") {
+        for (knot, positions) in visited.iter().rev().enumerate() {
+            applog!("Knot {}: {} unique positions", knot, positions.len());
+        }
     }
     
-    applog!("Number of unique tail positions: {}", tail_positions.len());
+    applog!("Number of unique tail positions: {}", visited[0].len());
+}
+
+/*------------------------------------------------------- record_positions - */
+
+fn record_positions(rope: &Vec<Position>, visited: &mut Vec<HashSet<Position>>) {
+    for (knot, position) in rope.iter().enumerate() {
+        visited[knot].insert(*position);
+    }
 }
 
 /*----------------------------------------------------------- execute_move - */
 
-fn execute_move(line: &str, rope: &mut Vec<Position>, tail_positions: &mut Vec<Position>) {
+fn execute_move(line: &str, rope: &mut Vec<Position>, visited: &mut Vec<HashSet<Position>>, animator: &mut Option<Animator>) {
     let tokens: Vec<&str> = line.split(' ').collect();
     let direction: char = tokens[0].chars().nth(0).unwrap();
     let steps: u32 = tokens[1].parse::<u32>().unwrap();
     for _i in 0..steps {
         move_one_step(direction, rope);
-        if !tail_positions.contains(&rope[0]) {
-            tail_positions.push((*rope)[0]);
+        record_positions(rope, visited);
+        if let Some(a) = animator.as_mut() {
+            a.after_step(line, rope, &visited[0]);
         }
     }
+    if let Some(a) = animator.as_mut() {
+        a.after_move(line, rope, &visited[0]);
+    }
 }
 
 /*---------------------------------------------------------- move_one_step - */
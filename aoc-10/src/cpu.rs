@@ -0,0 +1,130 @@
+use std::io::{BufRead, BufReader};
+use std::fs::File;
+use std::fmt;
+
+// A tiny CPU: registers, a program counter and a table of opcodes. Each
+// opcode declares how many cycles it takes and what it does once complete;
+// hooks see every cycle while it is in progress, before any effect lands.
+
+#[derive(Debug, Clone, Copy)]
+pub struct Registers {
+    pub x: i32
+}
+impl Default for Registers {
+    fn default () -> Registers {
+        Registers { x: 1 }
+    }
+}
+
+pub struct Opcode {
+    pub name: &'static str,
+    pub cycles: u32,
+    pub operands: usize,
+    pub execute: fn(&mut Registers, &[i32])
+}
+
+pub const INSTRUCTION_TABLE: [Opcode; 2] = [
+    Opcode { name: "noop", cycles: 1, operands: 0, execute: |_, _| {} },
+    Opcode { name: "addx", cycles: 2, operands: 1, execute: |r, args| r.x += args[0] },
+];
+
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    pub opcode: usize,
+    pub operands: Vec<i32>
+}
+impl Instruction {
+    pub fn definition(&self) -> &'static Opcode {
+        return &INSTRUCTION_TABLE[self.opcode];
+    }
+}
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.definition().name)?;
+        for operand in self.operands.iter() {
+            write!(f, " {}", operand)?;
+        }
+        return Ok(());
+    }
+}
+
+// Cycles are numbered from 1, as in the puzzle
+pub trait CycleHook {
+    fn on_cycle(&mut self, cycle: u32, registers: &Registers);
+
+    // Called as each instruction is fetched, before its first cycle
+    fn on_instruction(&mut self, _pc: usize, _instruction: &Instruction) {}
+}
+
+#[derive(Debug, Clone)]
+pub struct Cpu {
+    pub registers: Registers,
+    pub pc: usize,
+    pub cycle: u32,
+    program: Vec<Instruction>
+}
+impl Cpu {
+    pub fn new(program: Vec<Instruction>) -> Cpu {
+        Cpu { registers: Registers::default(), pc: 0, cycle: 0, program }
+    }
+
+    pub fn run(&mut self, hooks: &mut [&mut dyn CycleHook]) {
+        while self.pc < self.program.len() {
+            let instruction = &self.program[self.pc];
+            let definition = instruction.definition();
+
+            for hook in hooks.iter_mut() {
+                hook.on_instruction(self.pc, instruction);
+            }
+
+            for _ in 0..definition.cycles {
+                self.cycle += 1;
+                for hook in hooks.iter_mut() {
+                    hook.on_cycle(self.cycle, &self.registers);
+                }
+            }
+
+            (definition.execute)(&mut self.registers, &instruction.operands);
+            self.pc += 1;
+        }
+    }
+}
+
+/*----------------------------------------------------------- read_program - */
+
+pub fn read_program(reader: BufReader<File>) -> Vec<Instruction> {
+
+    let mut program: Vec<Instruction> = vec![];
+    for line in reader.lines() {
+        let line = line.unwrap();
+        program.push(parse_instruction(&line));
+    }
+
+    return program;
+}
+
+/*------------------------------------------------------ parse_instruction - */
+
+fn parse_instruction(line: &str) -> Instruction {
+
+    let tokens: Vec<&str> = line.split(' ').collect();
+    let name = tokens[0];
+
+    let opcode = match INSTRUCTION_TABLE.iter().position(|op| op.name == name) {
+        Some(opcode) => opcode,
+        None => panic!("Unsupported command: {}, line=[{}]", name, line),
+    };
+
+    let expected = INSTRUCTION_TABLE[opcode].operands;
+    if tokens.len() - 1 != expected {
+        panic!("Expected {} operand(s) for {}, line=[{}]", expected, name, line);
+    }
+
+    let operands: Vec<i32> = tokens[1..].iter()
+        .map(|t| t.parse::<i32>().unwrap_or_else(|_| panic!("Invalid operand: {}, line=[{}]", t, line)))
+        .collect();
+
+    return Instruction { opcode, operands };
+}
+
+/*---------------------------------------------------------- End of cpu.rs - */
\ No newline at end of file
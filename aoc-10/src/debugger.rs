@@ -0,0 +1,93 @@
+use std::io::{self, BufRead, Write};
+
+use crate::applog;
+use crate::startup;
+use crate::Crt;
+use crate::cpu::{CycleHook, Instruction, Registers};
+
+// Cycle-level tracing and breakpoints. -trace logs every cycle;
+// -break-at-cycle N stops during cycle N, dumps the machine state and the
+// screen drawn so far, then waits for a command on stdin.
+// The debugger draws its own copy of the screen, so it works for either part.
+
+pub struct Debugger {
+    crt: Crt,
+    trace: bool,
+    break_at: Option<u32>,
+    pc: usize,
+    instruction: String
+}
+impl Debugger {
+    // Returns None unless tracing or a breakpoint was asked for
+    pub fn from_options() -> Option<Debugger> {
+        let trace = startup::is("trace");
+        let break_at: Option<u32> = startup::value("break-at-cycle")
+            .map(|c| c.parse::<u32>().unwrap_or_else(|_| panic!("Invalid cycle: [{}]", c)));
+
+        if !trace && break_at.is_none() {
+            return None;
+        }
+
+        return Some(Debugger {
+            crt: Crt::from_options(),
+            trace,
+            break_at,
+            pc: 0,
+            instruction: String::new()
+        });
+    }
+
+    fn dump_state(&self, cycle: u32, registers: &Registers) {
+        applog!("Break during cycle {}: pc={}, instruction=[{}], registers={:?}",
+            cycle, self.pc, self.instruction, registers);
+        for row in self.crt.screen.iter().filter(|r| !r.is_empty()) {
+            applog!("{}", row);
+        }
+    }
+
+    // [Enter] steps one cycle, "c" continues to the end, "q" quits
+    fn prompt(&mut self, cycle: u32) {
+        print!("[Enter]=step, c=continue, q=quit > ");
+        io::stdout().flush().unwrap();
+
+        let mut command = String::new();
+        let read = io::stdin().lock().read_line(&mut command).unwrap();
+
+        match command.trim() {
+            _ if read == 0 => self.break_at = None,
+            "" => self.break_at = Some(cycle + 1),
+            "c" => self.break_at = None,
+            "q" => std::process::exit(0),
+            other => {
+                applog!("Unknown command: [{}]", other);
+                self.prompt(cycle);
+            }
+        }
+    }
+}
+impl CycleHook for Debugger {
+    fn on_instruction(&mut self, pc: usize, instruction: &Instruction) {
+        self.pc = pc;
+        self.instruction = instruction.to_string();
+    }
+
+    fn on_cycle(&mut self, cycle: u32, registers: &Registers) {
+        self.crt.on_cycle(cycle, registers);
+        let pixel = self.crt.get_pixel_char(cycle - 1, registers.x);
+        let (left, right) = self.crt.sprite_range(registers.x);
+
+        if self.trace {
+            applog!("Cycle {:>3}: [{:<8}] x={:>3}, sprite={}..{}, pixel {} = {}",
+                cycle, self.instruction, registers.x,
+                left, right,
+                cycle - 1, pixel);
+        }
+
+        if self.break_at == Some(cycle) {
+            self.dump_state(cycle, registers);
+            self.prompt(cycle);
+        }
+    }
+}
+
+/*---------------------------------------------------- End of debugger.rs - */
\ No newline at end of file
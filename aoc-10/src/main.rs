@@ -1,24 +1,86 @@
-use std::io::{BufRead, BufReader};
+use std::io::BufReader;
 use std::fs::File;
 
 #[macro_use]
 mod applog;
 mod startup;
+mod ocr;
+mod cpu;
+mod debugger;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-enum Operation {
-    NoOp,
-    AddX
+use cpu::{Cpu, CycleHook, Registers};
+use debugger::Debugger;
+
+// Sums the signal strength during the sampled cycles
+struct SignalSampler {
+    signal_strength_sum: i32
+}
+impl CycleHook for SignalSampler {
+    fn on_cycle(&mut self, cycle: u32, registers: &Registers) {
+        let signal_strength: i32 = (cycle as i32) * registers.x;
+
+        match cycle {
+            20 | 60 | 100 | 140 | 180 | 220 => {
+                self.signal_strength_sum += signal_strength;
+                applog!("Signal strength during cycle {} is {}.", cycle, signal_strength);
+            },
+            _ => {},
+        }
+    }
+}
+
+// Draws one pixel per cycle, left to right and top to bottom. Rows are
+// added as drawing reaches them; cycles beyond the last pixel are recorded
+// in overflow_cycle rather than drawn.
+#[derive(Debug, Clone)]
+struct Crt {
+    width: u32,
+    height: u32,
+    sprite_width: u32,
+    screen: Vec<String>,
+    overflow_cycle: Option<u32>
+}
+impl Default for Crt {
+    fn default () -> Crt {
+        Crt { width: 40, height: 6, sprite_width: 3, screen: vec![], overflow_cycle: None }
+    }
 }
+impl Crt {
+    // Puzzle geometry, overridden by -width, -height and -sprite-width
+    fn from_options() -> Crt {
+        let mut crt = Crt::default();
+        crt.width = get_dimension("width", crt.width);
+        crt.height = get_dimension("height", crt.height);
+        crt.sprite_width = get_dimension("sprite-width", crt.sprite_width);
+        return crt;
+    }
+
+    // Columns covered by the sprite, x being its centre (or centre-left if even)
+    fn sprite_range(&self, x: i32) -> (i32, i32) {
+        let left = x - (self.sprite_width as i32 - 1) / 2;
+        return (left, left + self.sprite_width as i32 - 1);
+    }
 
-#[derive(Debug, Copy, Clone)]
-struct Instruction {
-    op: Operation,
-    value: i32,
+    fn get_pixel_char(&self, position: u32, x: i32) -> char {
+        let column = (position % self.width) as i32;
+        let (left, right) = self.sprite_range(x);
+        return if column >= left && column <= right {'#'} else {'.'};
+    }
 }
-impl Default for Instruction {
-    fn default () -> Instruction {
-        Instruction{op: Operation::NoOp, value: 0}
+impl CycleHook for Crt {
+    fn on_cycle(&mut self, cycle: u32, registers: &Registers) {
+        let position = cycle - 1;
+        if position >= self.width * self.height {
+            self.overflow_cycle = self.overflow_cycle.or(Some(cycle));
+            return;
+        }
+
+        let row: usize = (position / self.width) as usize;
+        if row >= self.screen.len() {
+            self.screen.push(String::new());
+        }
+        let pixel_char = self.get_pixel_char(position, registers.x);
+        self.screen[row].push(pixel_char);
     }
 }
 
@@ -40,129 +102,63 @@ fn main() {
 
 fn part1(reader: BufReader<File>) {
 
-    let mut x: i32 = 1;
-    let mut cycle_number: u32 = 0; // number of cycle we have just started
-    let mut signal_strength_sum: i32 = 0;
-
-    // Read the file
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let command = read_next_instruction(&line);
-        let mut cycles_this_command: u32 = 0;
-        let mut command_completed: bool = false;
-        while !command_completed {
-            cycle_number += 1;
-           
-            let signal_strength: i32 = (cycle_number as i32) * x;
-            
-            match cycle_number {
-                20 | 60 | 100 | 140 | 180 | 220 => {
-                    signal_strength_sum += signal_strength;
-                    applog!("Signal strength during cycle {} is {}.", cycle_number, signal_strength);
-                },
-                _ => {},
-            }
-
-            command_completed = execute_cpu_cycle(&command, &mut x, &mut cycles_this_command);
-        }
+    let mut sampler = SignalSampler { signal_strength_sum: 0 };
+    let mut debugger = Debugger::from_options();
+
+    let mut hooks: Vec<&mut dyn CycleHook> = vec![&mut sampler];
+    if let Some(d) = debugger.as_mut() {
+        hooks.push(d);
     }
+
+    let mut cpu = Cpu::new(cpu::read_program(reader));
+    cpu.run(&mut hooks);
     
-    applog!("Signal strength sum: {}", signal_strength_sum);
+    applog!("Signal strength sum: {}", sampler.signal_strength_sum);
 }
 
 /*------------------------------------------------------------------ part2 - */
 
 fn part2(reader: BufReader<File>) {
 
-    let mut x: i32 = 1;
-    let mut cycle_number: u32 = 0; // number of last cycle
-
-    let mut screen: [String; 6] = Default::default();
-
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let command = read_next_instruction(&line);
-        let mut cycles_this_command: u32 = 0;
-        let mut command_completed: bool = false;
-        while !command_completed {
+    let mut crt = Crt::from_options();
+    let mut debugger = Debugger::from_options();
 
-            let pixel_char = get_pixel_char(cycle_number, x);
-            
-            let row: usize = (cycle_number as usize)/40;
-            screen[row].push(pixel_char);
-
-            command_completed = execute_cpu_cycle(&command, &mut x, &mut cycles_this_command);
-            cycle_number += 1;
-        }
+    let mut hooks: Vec<&mut dyn CycleHook> = vec![&mut crt];
+    if let Some(d) = debugger.as_mut() {
+        hooks.push(d);
     }
 
-    for row in screen {
+    let mut cpu = Cpu::new(cpu::read_program(reader));
+    cpu.run(&mut hooks);
+
+    for row in crt.screen.iter() {
         applog!("{}", row);
     }
-        
-}
-
-/*------------------------------------------------------ execute_cpu_cycle - */
 
-// returns true if we have completed this command
-
-fn execute_cpu_cycle(command: &Instruction, x: &mut i32, cycles_this_command: &mut u32) -> bool {
-
-    *cycles_this_command += 1;
+    if let Some(cycle) = crt.overflow_cycle {
+        applog!("PART2: Program ran past the end of the {}x{} screen at cycle {} ({} cycles in total).",
+            crt.width, crt.height, cycle, cpu.cycle);
+        return;
+    }
 
-    if command.op == Operation::NoOp {
-        return true;
-    } else if command.op == Operation::AddX {
-        if *cycles_this_command >= 2u32 {
-            *x += command.value;
-            return true;
-        } else {
-            return false;
-        }
-    } else {
-        panic!("Unsupported operation!");
+    match ocr::recognise(&crt.screen) {
+        Ok(text) => applog!("PART2: Screen reads: {}", text),
+        Err(e) => applog!("PART2: Unable to read screen: {}", e),
     }
 }
 
-/*-------------------------------------------------- read_next_instruction - */
+/*---------------------------------------------------------- get_dimension - */
 
-fn read_next_instruction(line: &str) -> Instruction {
-    let tokens: Vec<&str> = line.split(' ').collect();
-    let command = tokens[0];
-    
-    let mut instruction: Instruction = Instruction::default();
+fn get_dimension(name: &str, default: u32) -> u32 {
+    let value = match startup::value(name) {
+        Some(v) => v.parse::<u32>().unwrap_or_else(|_| panic!("Invalid -{}: [{}]", name, v)),
+        None => default,
+    };
 
-    if command == "noop" {
-        instruction.op = Operation::NoOp;
-    } else if command == "addx" {
-        instruction.op = Operation::AddX;
-        instruction.value = tokens[1].parse::<i32>().unwrap();
-    } else {
-        panic!("Unsupported command: {}, line=[{}]", command, line);
+    if value == 0 {
+        panic!("-{} must be at least 1", name);
     }
-
-    return instruction;
+    return value;
 }
 
-/*--------------------------------------------------------- get_pixel_char - */
-
-fn get_pixel_char(cycle_number: u32, x: i32) -> char {
-
-    let screen_size: u32 = 40;
-    let pixel_location: u32 = (cycle_number)%screen_size;
-    let reduced_sprite: bool = if x<0 {true} else {false};
-    let sprite_location: u32 = if x<0 {0} else {x as u32};
-
-    let pixel_delta: u32 = 
-        if sprite_location > pixel_location {
-            sprite_location - pixel_location
-        } else {
-            pixel_location - sprite_location
-        };
-
-    let min_pixel_delta: u32 = if reduced_sprite {0} else {1};
-    let pixel_char: char = if pixel_delta <= min_pixel_delta {'#'} else {'.'};
-
-    return pixel_char;
-}
 /*--------------------------------------------------------- End of main.rs - */
\ No newline at end of file
@@ -0,0 +1,80 @@
+// Reads the capital letters drawn on the CRT. Each letter is 4 pixels wide
+// and 6 high, followed by a blank column, in the font Advent of Code uses.
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+const GLYPH_SPACING: usize = 5;
+
+const GLYPHS: [(char, [&str; GLYPH_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/*-------------------------------------------------------------- recognise - */
+
+// Returns the letters on screen, or an error listing the (0-based) positions
+// of any that don't match a known glyph.
+
+pub fn recognise(screen: &[String]) -> Result<String, String> {
+
+    if screen.len() != GLYPH_HEIGHT {
+        return Err(format!("Expected {} rows, got {}", GLYPH_HEIGHT, screen.len()));
+    }
+
+    let width = screen.iter().map(|row| row.chars().count()).max().unwrap_or(0);
+    let glyph_count = (width + GLYPH_SPACING - 1) / GLYPH_SPACING;
+
+    let mut text: String = String::new();
+    let mut unrecognised: Vec<usize> = vec![];
+    for i in 0..glyph_count {
+        let glyph = extract_glyph(screen, i * GLYPH_SPACING);
+        match GLYPHS.iter().find(|(_, pattern)| glyph == *pattern) {
+            Some((letter, _)) => text.push(*letter),
+            None => {
+                text.push('?');
+                unrecognised.push(i);
+            }
+        }
+    }
+
+    if !unrecognised.is_empty() {
+        return Err(format!("Unrecognised glyph(s) at position(s) {:?}, read [{}]", unrecognised, text));
+    }
+
+    return Ok(text);
+}
+
+/*---------------------------------------------------------- extract_glyph - */
+
+// Lit pixels are '#', anything else (or off the edge of a row) is dark
+
+fn extract_glyph(screen: &[String], start: usize) -> [String; GLYPH_HEIGHT] {
+
+    let mut glyph: [String; GLYPH_HEIGHT] = Default::default();
+
+    for (row, line) in screen.iter().enumerate() {
+        let pixels: Vec<char> = line.chars().skip(start).take(GLYPH_WIDTH).collect();
+        for c in 0..GLYPH_WIDTH {
+            glyph[row].push(if pixels.get(c) == Some(&'#') {'#'} else {'.'});
+        }
+    }
+
+    return glyph;
+}
+
+/*---------------------------------------------------------- End of ocr.rs - */
\ No newline at end of file
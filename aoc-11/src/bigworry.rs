@@ -0,0 +1,125 @@
+use std::collections::VecDeque;
+use num_bigint::BigUint;
+
+use crate::applog;
+use crate::startup;
+use crate::{Monkey, perform_monkey_round};
+
+// Exact simulation: worries are never reduced modulo the divisor product,
+// so subtraction, division and non-coprime divisors are all handled
+// correctly. Worries grow without bound, so only practical for small
+// round counts. The exact simulation also stops, inconclusive, once any
+// worry grows past MAX_WORRY_BITS, as "old * old" doubles it every time.
+
+const MAX_WORRY_BITS: u64 = 100_000;
+
+/* ----------------------------------------------------------- cross_check - */
+
+// Plays [rounds] rounds with exact worries alongside the modular fast path,
+// reporting the first round at which their inspection counts disagree.
+// On return, monkeys hold the exact inspection counts.
+
+pub fn cross_check(monkeys: &mut Vec<Monkey>, rounds: u64, divide_by_3: bool, modulus: u64) {
+
+    let mut modular: Vec<Monkey> = monkeys.clone();
+    let mut queues: Vec<VecDeque<BigUint>> = monkeys.iter()
+        .map(|m| m.worry_levels.iter().map(|w| BigUint::from(*w)).collect())
+        .collect();
+    let mut counts: Vec<u64> = vec![0; monkeys.len()];
+
+    let mut first_mismatch: Option<u64> = None;
+    let mut stopped = false;
+    let mut rounds_played: u64 = 0;
+    for r in 1..=rounds {
+        // Counts only change once a round completes
+        let mut round_counts = counts.clone();
+        if let Err(e) = perform_big_round(monkeys, &mut queues, &mut round_counts, divide_by_3) {
+            applog!("Round {}: {} - stopping exact simulation.", r, e);
+            stopped = true;
+            break;
+        }
+        counts = round_counts;
+        rounds_played = r;
+
+        if let Err(e) = perform_monkey_round(&mut modular, divide_by_3, modulus, None) {
+            applog!("Round {}: modular path diverges: {}", r, e);
+            first_mismatch = Some(r);
+            break;
+        }
+
+        let modular_counts: Vec<u64> = modular.iter().map(|m| m.inspection_count).collect();
+        if first_mismatch.is_none() && modular_counts != counts {
+            applog!("Round {}: modular inspections {:?} differ from exact {:?}", r, modular_counts, counts);
+            first_mismatch = Some(r);
+        }
+
+        let largest_bits = get_largest_bits(&queues);
+        if startup::is_debug() {
+            applog!("Round {}: largest worry has {} bits", r, largest_bits);
+        }
+        if first_mismatch.is_none() && r < rounds && largest_bits > MAX_WORRY_BITS {
+            applog!("Round {}: largest worry has {} bits, over the {} bit limit - stopping exact simulation.",
+                r, largest_bits, MAX_WORRY_BITS);
+            stopped = true;
+            break;
+        }
+    }
+
+    match (first_mismatch, stopped) {
+        (Some(r), _) => applog!("Cross-check FAILED: modular path diverges from exact at round {}.", r),
+        (None, true) => applog!("Cross-check inconclusive: exact simulation stopped after {} of {} rounds, all matching.", rounds_played, rounds),
+        (None, false) => applog!("Cross-check passed: modular worries match exact for {} rounds.", rounds_played),
+    }
+    applog!("Largest exact worry level: {} bits", get_largest_bits(&queues));
+
+    // Exact worries don't fit the monkeys' queues - report inspections only,
+    // as of the last complete exact round
+    for (i, m) in monkeys.iter_mut().enumerate() {
+        m.inspection_count = counts[i];
+        m.worry_levels.clear();
+    }
+}
+
+/* ----------------------------------------------------- perform_big_round - */
+
+fn perform_big_round(monkeys: &Vec<Monkey>, queues: &mut Vec<VecDeque<BigUint>>, counts: &mut Vec<u64>, divide_by_3: bool) -> Result<(), String> {
+
+    let zero = BigUint::from(0u32);
+    for i in 0..monkeys.len() {
+        while let Some(w) = queues[i].pop_front() {
+
+            let mut new_worry = match monkeys[i].operation.evaluate_big(&w) {
+                Some(worry) => worry,
+                None => return Err(format!("Monkey {}: {} is negative or undefined for old={}", i, monkeys[i].operation, w)),
+            };
+
+            if divide_by_3 {
+                new_worry /= 3u32;
+            }
+
+            let target_monkey_index: usize =
+                if &new_worry % monkeys[i].divisor == zero {
+                    monkeys[i].monkey_if_true
+                } else {
+                    monkeys[i].monkey_if_false
+                };
+
+            if target_monkey_index == i {
+                panic!("Monkey can't throw item to themself.");
+            }
+
+            queues[target_monkey_index].push_back(new_worry);
+            counts[i] += 1;
+        }
+    }
+
+    return Ok(());
+}
+
+/* ------------------------------------------------------ get_largest_bits - */
+
+fn get_largest_bits(queues: &Vec<VecDeque<BigUint>>) -> u64 {
+    return queues.iter().flatten().map(|w| w.bits()).max().unwrap_or(0);
+}
+
+/* ---------------------------------------------------- End of bigworry.rs - */
\ No newline at end of file
@@ -0,0 +1,208 @@
+use std::fmt;
+use num_bigint::BigUint;
+
+// A small integer expression tree over a single variable, "old".
+// Grammar (usual precedence, left-associative):
+//   expression := term (('+' | '-') term)*
+//   term       := factor (('*' | '/') factor)*
+//   factor     := integer | "old" | '(' expression ')'
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide
+}
+impl Operator {
+    fn symbol(&self) -> char {
+        match self {
+            Operator::Add => '+',
+            Operator::Subtract => '-',
+            Operator::Multiply => '*',
+            Operator::Divide => '/',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Constant(u64),
+    Old,
+    Binary(Box<Expression>, Operator, Box<Expression>)
+}
+impl Default for Expression {
+    fn default () -> Expression {
+        Expression::Old
+    }
+}
+impl Expression {
+    // Returns None if any step overflows, goes below zero or divides by zero
+    pub fn checked_evaluate(&self, old: u64) -> Option<u64> {
+        return self.evaluate_with(&old, &|operator, l: u64, r: u64| match operator {
+            Operator::Add => l.checked_add(r),
+            Operator::Subtract => l.checked_sub(r),
+            Operator::Multiply => l.checked_mul(r),
+            Operator::Divide => l.checked_div(r),
+        });
+    }
+
+    // Returns None only if a step goes below zero or divides by zero
+    pub fn evaluate_big(&self, old: &BigUint) -> Option<BigUint> {
+        return self.evaluate_with(old, &|operator, l: BigUint, r: BigUint| match operator {
+            Operator::Add => Some(l + r),
+            Operator::Subtract => if l >= r {Some(l - r)} else {None},
+            Operator::Multiply => Some(l * r),
+            Operator::Divide => if r != BigUint::from(0u32) {Some(l / r)} else {None},
+        });
+    }
+
+    fn evaluate_with<T>(&self, old: &T, apply: &impl Fn(Operator, T, T) -> Option<T>) -> Option<T>
+        where T: Clone + From<u64> {
+        match self {
+            Expression::Constant(value) => Some(T::from(*value)),
+            Expression::Old => Some(old.clone()),
+            Expression::Binary(left, operator, right) => {
+                let l = left.evaluate_with(old, apply)?;
+                let r = right.evaluate_with(old, apply)?;
+                apply(*operator, l, r)
+            }
+        }
+    }
+}
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expression::Constant(value) => write!(f, "{}", value),
+            Expression::Old => write!(f, "old"),
+            Expression::Binary(left, operator, right) =>
+                write!(f, "({} {} {})", left, operator.symbol(), right),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(u64),
+    Old,
+    Operator(Operator),
+    Open,
+    Close
+}
+
+/* ----------------------------------------------------------------- parse - */
+
+pub fn parse(text: &str) -> Result<Expression, String> {
+
+    let tokens = tokenise(text)?;
+    let mut position: usize = 0;
+
+    let expression = parse_expression(&tokens, &mut position)?;
+    if position < tokens.len() {
+        return Err(format!("Unexpected {:?} at token {} in [{}]", tokens[position], position, text));
+    }
+
+    return Ok(expression);
+}
+
+/* -------------------------------------------------------------- tokenise - */
+
+fn tokenise(text: &str) -> Result<Vec<Token>, String> {
+
+    let mut tokens: Vec<Token> = vec![];
+    let chars: Vec<char> = text.chars().collect();
+
+    let mut i: usize = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            ' ' => (),
+            '+' => tokens.push(Token::Operator(Operator::Add)),
+            '-' => tokens.push(Token::Operator(Operator::Subtract)),
+            '*' => tokens.push(Token::Operator(Operator::Multiply)),
+            '/' => tokens.push(Token::Operator(Operator::Divide)),
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            _ if c.is_ascii_digit() => {
+                let start = i;
+                while i+1 < chars.len() && chars[i+1].is_ascii_digit() {
+                    i += 1;
+                }
+                let digits: String = chars[start..=i].iter().collect();
+                let value = digits.parse::<u64>()
+                    .map_err(|e| format!("Invalid number [{}] in [{}]: {}", digits, text, e))?;
+                tokens.push(Token::Number(value));
+            },
+            _ if chars[i..].starts_with(&['o', 'l', 'd']) => {
+                tokens.push(Token::Old);
+                i += 2;
+            },
+            _ => return Err(format!("Unrecognised character [{}] at {} in [{}]", c, i, text)),
+        }
+        i += 1;
+    }
+
+    return Ok(tokens);
+}
+
+/* ------------------------------------------------------ parse_expression - */
+
+fn parse_expression(tokens: &Vec<Token>, position: &mut usize) -> Result<Expression, String> {
+
+    let mut expression = parse_term(tokens, position)?;
+
+    while let Some(Token::Operator(operator)) = tokens.get(*position) {
+        if *operator != Operator::Add && *operator != Operator::Subtract {
+            break;
+        }
+        *position += 1;
+        let right = parse_term(tokens, position)?;
+        expression = Expression::Binary(Box::new(expression), *operator, Box::new(right));
+    }
+
+    return Ok(expression);
+}
+
+/* ------------------------------------------------------------ parse_term - */
+
+fn parse_term(tokens: &Vec<Token>, position: &mut usize) -> Result<Expression, String> {
+
+    let mut expression = parse_factor(tokens, position)?;
+
+    while let Some(Token::Operator(operator)) = tokens.get(*position) {
+        if *operator != Operator::Multiply && *operator != Operator::Divide {
+            break;
+        }
+        *position += 1;
+        let right = parse_factor(tokens, position)?;
+        expression = Expression::Binary(Box::new(expression), *operator, Box::new(right));
+    }
+
+    return Ok(expression);
+}
+
+/* ---------------------------------------------------------- parse_factor - */
+
+fn parse_factor(tokens: &Vec<Token>, position: &mut usize) -> Result<Expression, String> {
+
+    let token = tokens.get(*position).ok_or("Unexpected end of expression")?;
+    *position += 1;
+
+    match token {
+        Token::Number(value) => Ok(Expression::Constant(*value)),
+        Token::Old => Ok(Expression::Old),
+        Token::Open => {
+            let expression = parse_expression(tokens, position)?;
+            match tokens.get(*position) {
+                Some(Token::Close) => {
+                    *position += 1;
+                    Ok(expression)
+                },
+                _ => Err(format!("Expected ')' at token {}", *position)),
+            }
+        },
+        _ => Err(format!("Unexpected {:?} at token {}", token, *position-1)),
+    }
+}
+
+/* -------------------------------------------------- End of expression.rs - */
\ No newline at end of file
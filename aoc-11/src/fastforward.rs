@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use crate::applog;
+use crate::startup;
+use crate::Monkey;
+
+// Items never interact: each one follows its own path from monkey to monkey,
+// fully determined by its (monkey, worry) state at the start of a round.
+// Worries stay below the divisor product, so every path must eventually
+// repeat a state, after which its inspections repeat with the same period.
+
+#[derive(Debug, Clone)]
+struct Trajectory {
+    states: Vec<(usize, u64)>,
+    inspections: Vec<Vec<u64>>,
+    cycle_start: usize
+}
+impl Trajectory {
+    fn cycle_length(&self) -> usize {
+        return self.states.len() - 1 - self.cycle_start;
+    }
+
+    // Index into states/inspections that is equivalent to the given round
+    fn equivalent_round(&self, round: u64) -> usize {
+        let last = (self.states.len() - 1) as u64;
+        if round <= last {
+            return round as usize;
+        }
+        let offset = (round - self.cycle_start as u64) % self.cycle_length() as u64;
+        return self.cycle_start + offset as usize;
+    }
+
+    fn inspections_after(&self, round: u64, monkey: usize) -> u64 {
+        let last = (self.states.len() - 1) as u64;
+        if round <= last {
+            return self.inspections[round as usize][monkey];
+        }
+
+        let cycles = (round - self.cycle_start as u64) / self.cycle_length() as u64;
+        let per_cycle = self.inspections[self.states.len() - 1][monkey] - self.inspections[self.cycle_start][monkey];
+
+        return self.inspections[self.equivalent_round(round)][monkey] + cycles * per_cycle;
+    }
+}
+
+/* ---------------------------------------------------------- fast_forward - */
+
+// Equivalent to calling perform_monkey_round [rounds] times, except that
+// items finish in each monkey's queue in their original input order.
+
+pub fn fast_forward(monkeys: &mut Vec<Monkey>, rounds: u64, divide_by_3: bool, modulus: u64) {
+
+    let mut items: Vec<(usize, u64)> = vec![];
+    for (i, m) in monkeys.iter().enumerate() {
+        for w in m.worry_levels.iter() {
+            items.push((i, *w));
+        }
+    }
+
+    for m in monkeys.iter_mut() {
+        m.worry_levels.clear();
+    }
+
+    for (monkey, worry) in items {
+        let trajectory = trace_item(monkeys, monkey, worry, rounds, divide_by_3, modulus);
+
+        if startup::is_debug() && trajectory.cycle_length() > 0 {
+            applog!("Item ({}, {}): cycle of {} rounds from round {}",
+                monkey, worry, trajectory.cycle_length(), trajectory.cycle_start);
+        } else if startup::is_debug() {
+            applog!("Item ({}, {}): no cycle within {} rounds", monkey, worry, rounds);
+        }
+
+        for i in 0..monkeys.len() {
+            monkeys[i].inspection_count += trajectory.inspections_after(rounds, i);
+        }
+
+        let (final_monkey, final_worry) = trajectory.states[trajectory.equivalent_round(rounds)];
+        monkeys[final_monkey].worry_levels.push_back(final_worry);
+    }
+}
+
+/* ------------------------------------------------------------ trace_item - */
+
+// Follows one item round by round until it either repeats a state or
+// reaches the requested number of rounds.
+
+fn trace_item(monkeys: &Vec<Monkey>, monkey: usize, worry: u64, rounds: u64, divide_by_3: bool, modulus: u64) -> Trajectory {
+
+    let mut trajectory = Trajectory {
+        states: vec![(monkey, worry)],
+        inspections: vec![vec![0; monkeys.len()]],
+        cycle_start: 0
+    };
+    let mut seen: HashMap<(usize, u64), usize> = HashMap::new();
+    seen.insert((monkey, worry), 0);
+
+    let mut state = (monkey, worry);
+    let mut counts: Vec<u64> = vec![0; monkeys.len()];
+    for r in 1..=rounds {
+        state = trace_item_round(monkeys, state, &mut counts, divide_by_3, modulus);
+        trajectory.states.push(state);
+        trajectory.inspections.push(counts.clone());
+
+        if let Some(previous) = seen.insert(state, r as usize) {
+            trajectory.cycle_start = previous;
+            return trajectory;
+        }
+    }
+
+    // No repeat within the requested rounds - every round has been recorded,
+    // so the (empty) cycle is never used
+    trajectory.cycle_start = trajectory.states.len() - 1;
+    return trajectory;
+}
+
+/* ------------------------------------------------------ trace_item_round - */
+
+// An item thrown to a higher-numbered monkey is inspected again in the same
+// round; one thrown to a lower-numbered monkey waits for the next round.
+
+fn trace_item_round(monkeys: &Vec<Monkey>, state: (usize, u64), counts: &mut Vec<u64>, divide_by_3: bool, modulus: u64) -> (usize, u64) {
+
+    let (mut monkey, mut worry) = state;
+    loop {
+        let (new_worry, target_monkey_index) = monkeys[monkey].inspect(worry, divide_by_3, modulus);
+        counts[monkey] += 1;
+
+        if target_monkey_index == monkey {
+            panic!("Monkey can't throw item to themself.");
+        }
+
+        worry = new_worry;
+        let next_round = target_monkey_index < monkey;
+        monkey = target_monkey_index;
+
+        if next_round {
+            return (monkey, worry);
+        }
+    }
+}
+
+/* ------------------------------------------------- End of fastforward.rs - */
\ No newline at end of file
@@ -0,0 +1,130 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::Monkey;
+
+// Round-by-round record of a literal simulation. Round 0 is the starting
+// state; each later round holds the state after that round and the throws
+// made during it, in the order they happened.
+
+#[derive(Debug, Clone)]
+struct MonkeyState {
+    monkey: u32,
+    inspections: u64,
+    worry_levels: Vec<u64>
+}
+
+#[derive(Debug, Clone)]
+struct Throw {
+    from: usize,
+    to: usize,
+    worry_before: u64,
+    worry_after: u64
+}
+
+#[derive(Debug, Clone)]
+struct Round {
+    round: usize,
+    monkeys: Vec<MonkeyState>,
+    throws: Vec<Throw>
+}
+
+#[derive(Debug, Clone)]
+pub struct History {
+    rounds: Vec<Round>,
+    throws: Vec<Throw>
+}
+impl History {
+    pub fn new(monkeys: &Vec<Monkey>) -> History {
+        let mut history = History { rounds: vec![], throws: vec![] };
+        history.record_round(monkeys);
+        return history;
+    }
+
+    pub fn record_throw(&mut self, from: usize, to: usize, worry_before: u64, worry_after: u64) {
+        self.throws.push(Throw { from, to, worry_before, worry_after });
+    }
+
+    // Call once each round is complete, to snapshot the monkeys and close off its throws
+    pub fn record_round(&mut self, monkeys: &Vec<Monkey>) {
+        let states: Vec<MonkeyState> = monkeys.iter()
+            .map(|m| MonkeyState {
+                monkey: m.index,
+                inspections: m.inspection_count,
+                worry_levels: m.worry_levels.iter().copied().collect()
+            })
+            .collect();
+
+        self.rounds.push(Round {
+            round: self.rounds.len(),
+            monkeys: states,
+            throws: std::mem::take(&mut self.throws)
+        });
+    }
+
+    // Format follows the file's extension: .csv or .json
+    pub fn check_filename(filename: &str) -> Result<(), String> {
+        if filename.ends_with(".json") || filename.ends_with(".csv") {
+            return Ok(());
+        }
+        return Err(format!("Unsupported history format: [{}], expected .csv or .json", filename));
+    }
+
+    pub fn export(&self, filename: &str) {
+        if let Err(e) = History::check_filename(filename) {
+            panic!("{}", e);
+        }
+
+        let mut writer = BufWriter::new(File::create(filename).unwrap());
+        if filename.ends_with(".json") {
+            self.write_json(&mut writer);
+        } else {
+            self.write_csv(&mut writer);
+        }
+
+        writer.flush().unwrap();
+    }
+
+    // One row per monkey per round ("state"), then one per throw made in that round ("throw")
+    fn write_csv(&self, writer: &mut impl Write) {
+        writeln!(writer, "round,record,monkey,inspections,worry_levels,target,worry_before,worry_after").unwrap();
+
+        for r in self.rounds.iter() {
+            for m in r.monkeys.iter() {
+                let levels: Vec<String> = m.worry_levels.iter().map(|w| w.to_string()).collect();
+                writeln!(writer, "{},state,{},{},\"{}\",,,", r.round, m.monkey, m.inspections, levels.join(" ")).unwrap();
+            }
+            for t in r.throws.iter() {
+                writeln!(writer, "{},throw,{},,,{},{},{}", r.round, t.from, t.to, t.worry_before, t.worry_after).unwrap();
+            }
+        }
+    }
+
+    fn write_json(&self, writer: &mut impl Write) {
+        writeln!(writer, "{{\n  \"rounds\": [").unwrap();
+
+        for (i, r) in self.rounds.iter().enumerate() {
+            let monkeys: Vec<String> = r.monkeys.iter()
+                .map(|m| format!("        {{\"monkey\": {}, \"inspections\": {}, \"worry_levels\": {:?}}}",
+                    m.monkey, m.inspections, m.worry_levels))
+                .collect();
+            let throws: Vec<String> = r.throws.iter()
+                .map(|t| format!("        {{\"from\": {}, \"to\": {}, \"worry_before\": {}, \"worry_after\": {}}}",
+                    t.from, t.to, t.worry_before, t.worry_after))
+                .collect();
+
+            writeln!(writer, "    {{\n      \"round\": {},", r.round).unwrap();
+            writeln!(writer, "      \"monkeys\": [\n{}\n      ],", monkeys.join(",\n")).unwrap();
+            if throws.is_empty() {
+                writeln!(writer, "      \"throws\": []").unwrap();
+            } else {
+                writeln!(writer, "      \"throws\": [\n{}\n      ]", throws.join(",\n")).unwrap();
+            }
+            writeln!(writer, "    }}{}", if i+1 < self.rounds.len() {","} else {""}).unwrap();
+        }
+
+        writeln!(writer, "  ]\n}}").unwrap();
+    }
+}
+
+/* ----------------------------------------------------- End of history.rs - */
\ No newline at end of file
@@ -5,28 +5,19 @@ use std::collections::VecDeque;
 #[macro_use]
 mod applog;
 mod startup;
+mod expression;
+mod fastforward;
+mod history;
+mod bigworry;
 
-#[derive(Debug, Copy, Clone)]
-enum Operator {
-    Add,
-    Subtract,
-    Multiply,
-    Divide
-}
-
-#[derive(Debug, Copy, Clone, PartialEq)]
-enum Operand {
-    Value,
-    MySelf
-}
+use expression::Expression;
+use history::History;
 
 #[derive(Debug, Clone)]
 struct Monkey {
     index: u32,
     worry_levels: VecDeque<u64>,
-    operator: Operator,
-    operand: Operand,
-    value: u64,
+    operation: Expression,
     divisor: u64,
     monkey_if_true: usize,
     monkey_if_false: usize,
@@ -37,9 +28,7 @@ impl Default for Monkey {
         Monkey {
             index: 0, 
             worry_levels: VecDeque::new(), 
-            operator: Operator::Add, 
-            operand: Operand::Value, 
-            value: 0,
+            operation: Expression::default(), 
             divisor: 0,
             monkey_if_true: 0,
             monkey_if_false: 0,
@@ -51,14 +40,48 @@ impl Monkey {
     fn clear (&mut self)  {
         self.index = 0; 
         self.worry_levels.clear(); 
-        self.operator = Operator::Add;
-        self.operand = Operand::Value; 
-        self.value = 0;
+        self.operation = Expression::default();
         self.divisor = 0;
         self.monkey_if_true = 0;
         self.monkey_if_false = 0;
         self.inspection_count = 0;
     }
+
+    // Returns the item's new worry level and the monkey it is thrown to
+    fn inspect(&self, worry: u64, divide_by_3: bool, modulus: u64) -> (u64, usize) {
+        return match self.try_inspect(worry, divide_by_3, modulus) {
+            Ok(result) => result,
+            Err(e) => panic!("{}", e),
+        };
+    }
+
+    // As inspect, but reports a worry that overflows or goes below zero
+    fn try_inspect(&self, worry: u64, divide_by_3: bool, modulus: u64) -> Result<(u64, usize), String> {
+        let mut new_worry = match self.operation.checked_evaluate(worry) {
+            Some(new_worry) => new_worry,
+            None => return Err(format!("Monkey {}: arithmetic overflow evaluating {} with old={}",
+                self.index, self.operation, worry)),
+        };
+
+        if divide_by_3 {
+            new_worry /= 3
+        }
+
+        // Get remainder of product of divisors - to ensure worry remains bounded
+        // NB: This is the critical step to make step2 work!
+        // If it's divisible, we get 0 => hence next divisor test always true
+        // If it's not, remainder will pass/not-pass the test in the same way
+        new_worry %= modulus;
+
+        let target_monkey_index: usize = 
+            if new_worry % self.divisor == 0 {
+                self.monkey_if_true
+            } else {
+                self.monkey_if_false
+            };
+
+        return Ok((new_worry, target_monkey_index));
+    }
 }
 
 /* ------------------------------------------------------------------ main - */
@@ -69,16 +92,45 @@ fn main() {
     let mut monkeys: Vec<Monkey> = vec![];
     read_monkeys(reader, &mut monkeys);
 
-    let rounds = if startup::is_part1() {20} else {10000};
+    // Exact worries grow too fast for the full part 2 count
+    let default_rounds = if startup::is_part1() || startup::is("bigint") {20} else {10000};
+    let rounds: u64 = match startup::value("rounds") {
+        Some(r) => r.parse::<u64>().unwrap_or_else(|_| panic!("Invalid round count: [{}]", r)),
+        None => default_rounds,
+    };
     let divide_by_3 = if startup::is_part1() {true} else {false};
+    let modulus = get_worry_modulus(&monkeys);
+
+    // Recording history needs every round to actually be played
+    let history_file = startup::value("history");
+    if let Some(Err(e)) = history_file.as_deref().map(History::check_filename) {
+        panic!("{}", e);
+    }
+    if history_file.is_some() && startup::is("bigint") {
+        panic!("-history can't be used with -bigint, which doesn't record rounds");
+    }
+    let mut history: Option<History> = history_file.as_ref().map(|_| History::new(&monkeys));
 
-    // Perform specified number of rounds
-    for r in 0..rounds {
-        if startup::is_debug() {
-            applog!("Starting round: {}", r+1);
-            dump_monkey_activity(&monkeys, false);
+    if startup::is("bigint") {
+        bigworry::cross_check(&mut monkeys, rounds, divide_by_3, modulus);
+    } else if startup::is("literal") || history.is_some() {
+        // Perform specified number of rounds
+        for r in 0..rounds {
+            if startup::is_debug() {
+                applog!("Starting round: {}", r+1);
+                dump_monkey_activity(&monkeys, false);
+            }
+            if let Err(e) = perform_monkey_round(&mut monkeys, divide_by_3, modulus, history.as_mut()) {
+                panic!("Round {}: {}", r+1, e);
+            }
         }
-        perform_monkey_round(&mut monkeys, divide_by_3);
+    } else {
+        fastforward::fast_forward(&mut monkeys, rounds, divide_by_3, modulus);
+    }
+
+    if let (Some(history), Some(filename)) = (history, history_file) {
+        history.export(&filename);
+        applog!("Round history written to: {}", filename);
     }
 
     dump_monkey_activity(&monkeys, true);
@@ -138,25 +190,14 @@ fn read_worry_levels(line: &str, monkey: &mut Monkey) {
 
 fn read_operation(line: &str, monkey: &mut Monkey) {
 
-    let prefix = "  Operation: new = old ";
+    let prefix = "  Operation: new = ";
     if line.starts_with(prefix)==false {
         panic!("Expected [{}], got [{}]", prefix, line);
     }
-    let tokens: Vec<&str> = line[prefix.len()..].split(" ").collect();
-    match tokens[0] {
-        "+" => monkey.operator = Operator::Add,
-        "-" => monkey.operator = Operator::Subtract,
-        "*" => monkey.operator = Operator::Multiply,
-        "/" => monkey.operator = Operator::Divide,
-        _ => panic!("Unrecognised operator: [{}], line: [{}]", tokens[0], line),
-    }
-
-    if tokens[1] == "old" {
-        monkey.operand = Operand::MySelf;
-    } else {
-        monkey.operand = Operand::Value;
-        monkey.value = tokens[1].parse::<u64>().unwrap();
-    }
+    monkey.operation = match expression::parse(&line[prefix.len()..]) {
+        Ok(operation) => operation,
+        Err(e) => panic!("Invalid operation: {}, line: [{}]", e, line),
+    };
 }
 
 /* ---------------------------------------------------------- read_divisor - */
@@ -192,9 +233,18 @@ fn read_false_monkey(line: &str, monkey: &mut Monkey) {
     monkey.monkey_if_false = line[prefix.len()..line.len()].parse::<usize>().unwrap();
 }
 
+/* ----------------------------------------------------- get_worry_modulus - */
+
+fn get_worry_modulus(monkeys: &Vec<Monkey>) -> u64 {
+    return monkeys.iter().map(|m| m.divisor).reduce(|a, b| a*b).unwrap();
+}
+
 /* -------------------------------------------------- perform_monkey_round - */
 
-fn perform_monkey_round(monkeys: &mut Vec<Monkey>, divide_by_3: bool) {
+// Stops at the first item whose worry can't be computed, leaving the round
+// part played
+
+fn perform_monkey_round(monkeys: &mut Vec<Monkey>, divide_by_3: bool, modulus: u64, mut history: Option<&mut History>) -> Result<(), String> {
 
     let mut monkeys_left: Vec<usize> = vec![];
     for i in 0..monkeys.len() {
@@ -203,38 +253,8 @@ fn perform_monkey_round(monkeys: &mut Vec<Monkey>, divide_by_3: bool) {
             // Get next worry level
             let w = monkeys[i].worry_levels.pop_front().unwrap();
 
-            // Perform operation
-            let argument = 
-                if monkeys[i].operand == Operand::Value {
-                    monkeys[i].value
-                } else {
-                    w
-                };
-            
-            let mut new_worry = match monkeys[i].operator {
-                Operator::Add => w + argument,
-                Operator::Subtract => w - argument,
-                Operator::Multiply => w * argument,
-                Operator::Divide => w / argument,
-            };
-            
-            if divide_by_3 {
-                new_worry /= 3
-            }
-            
-            // Get remainder of product of divisors - to ensure worry remains bounded
-            // NB: This is the critical step to make step2 work!
-            // If it's divisible, we get 0 => hence next divisor test always true
-            // If it's not, remainder will pass/not-pass the test in the same way   
-            new_worry %= monkeys.iter().map(|m| m.divisor).reduce(|a, b| a*b).unwrap();
-
-            // Throw item to another monkey
-            let target_monkey_index: usize = 
-                if new_worry % monkeys[i].divisor == 0 {
-                    monkeys[i].monkey_if_true
-                } else {
-                    monkeys[i].monkey_if_false
-                };
+            // Perform operation and throw item to another monkey
+            let (new_worry, target_monkey_index) = monkeys[i].try_inspect(w, divide_by_3, modulus)?;
 
             // Don't throw to yourself
             if target_monkey_index == i {
@@ -247,6 +267,9 @@ fn perform_monkey_round(monkeys: &mut Vec<Monkey>, divide_by_3: bool) {
 
             // Perform the throw
             monkeys[target_monkey_index].worry_levels.push_back(new_worry);
+            if let Some(h) = history.as_deref_mut() {
+                h.record_throw(i, target_monkey_index, w, new_worry);
+            }
 
             // Increment inspection count
             monkeys[i].inspection_count += 1;
@@ -257,6 +280,11 @@ fn perform_monkey_round(monkeys: &mut Vec<Monkey>, divide_by_3: bool) {
     if startup::is_debug() {
         applog!("Monkeys left: {:?}", monkeys_left);
     }
+    if let Some(h) = history {
+        h.record_round(monkeys);
+    }
+
+    return Ok(());
 }
 
 /* -------------------------------------------------- dump_monkey_activity - */
@@ -274,7 +302,7 @@ fn dump_monkey_activity(monkeys: & Vec<Monkey>, get_monkey_business: bool) {
         // Compute monkey business
         applog!("Inspection counts: {:?}", inspection_counts);
         inspection_counts.sort_by(|a, b| b.cmp(a));
-        applog!("Monkey business: {}", inspection_counts[0] * inspection_counts[1]);
+        applog!("Monkey business: {}", inspection_counts[0] as u128 * inspection_counts[1] as u128);
     }
 }
 
@@ -4,6 +4,7 @@ use std::fs::File;
 #[macro_use]
 mod applog;
 mod startup;
+mod render;
 
 #[derive(Debug, Clone, Copy)]
 struct Range {
@@ -27,7 +28,7 @@ impl Range {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 struct Point {
     x: i32,
     y: i32
@@ -80,7 +81,11 @@ impl Reading {
 fn main() {
     let reader = startup::get_reader().unwrap();
 
-    if startup::is("part1") {  
+    if startup::is("render") {
+        let mut readings: Vec<Reading> = vec![];
+        import_readings(reader, &mut readings);
+        render::render(&readings);
+    } else if startup::is("part1") {  
         part1(reader);
     } else {
         part2(reader);
@@ -120,7 +125,7 @@ fn part2(reader: BufReader<File>) {
     let mut readings: Vec<Reading> = vec![];
     import_readings(reader, &mut readings);
 
-    let max = if startup::is("test") {20} else {4000000};
+    let max = get_search_limit();
     for r in 0..max {
         let (found, hidden) = get_hidden_cells(&readings, r);
         if found {
@@ -132,6 +137,12 @@ fn part2(reader: BufReader<File>) {
     }
 }
 
+/*------------------------------------------------------- get_search_limit - */
+
+fn get_search_limit() -> i32 {
+    return if startup::is("test") {20} else {4000000};
+}
+
 /*-------------------------------------------------------- import_readings - */
 
 fn import_readings(reader: BufReader<File>, readings: &mut  Vec<Reading>) {
@@ -0,0 +1,241 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::applog;
+use crate::startup;
+use crate::{Point, Reading, get_search_limit, get_sensor_ranges_hitting_row, merge_sensor_ranges};
+
+// Grey levels used in the PGM bitmap
+const PIXEL_SENSOR: u8 = 0;
+const PIXEL_BEACON: u8 = 96;
+const PIXEL_COVERED: u8 = 200;
+const PIXEL_UNCOVERED: u8 = 255;
+
+// Largest bitmap dimension when no -scale is given
+const DEFAULT_BITMAP_SIZE: i64 = 1000;
+
+// Largest ASCII window, in cells per side
+const MAX_ASCII_SIZE: i64 = 200;
+
+/*----------------------------------------------------------------- render - */
+
+// -render draws the readings as ASCII over -window x0,y0,x1,y1 (inclusive),
+// or, with -bitmap [file], writes a scaled-down PGM of the coverage. ASCII
+// windows are limited to MAX_ASCII_SIZE a side, which the real input's
+// readings far exceed, so it needs a -window or -bitmap.
+
+pub fn render(readings: &Vec<Reading>) {
+
+    if let Some(filename) = startup::value("bitmap") {
+        let (top_left, bottom_right) = match startup::value("window") {
+            Some(window) => parse_window(&window),
+            None => get_coverage_bounds(readings),
+        };
+        write_bitmap(readings, &filename, top_left, bottom_right);
+    } else {
+        let (top_left, bottom_right) = match startup::value("window") {
+            Some(window) => parse_window(&window),
+            None => get_reading_bounds(readings),
+        };
+
+        let width = bottom_right.x as i64 - top_left.x as i64 + 1;
+        let height = bottom_right.y as i64 - top_left.y as i64 + 1;
+        if width > MAX_ASCII_SIZE || height > MAX_ASCII_SIZE {
+            applog!("Can't draw {}x{} cells as ASCII (limit {} a side) - use a smaller -window x0,y0,x1,y1 or -bitmap [file].",
+                width, height, MAX_ASCII_SIZE);
+            return;
+        }
+        draw_ascii(readings, top_left, bottom_right);
+    }
+}
+
+/*----------------------------------------------------------- parse_window - */
+
+// Assumes format: x0,y0,x1,y1
+
+fn parse_window(window: &str) -> (Point, Point) {
+
+    let values: Vec<i32> = window.split(',')
+        .map(|v| v.trim().parse::<i32>()
+            .unwrap_or_else(|_| panic!("Invalid window value [{}] in [{}]", v, window)))
+        .collect();
+
+    if values.len() != 4 {
+        panic!("Expected window as x0,y0,x1,y1, got: [{}]", window);
+    }
+
+    let top_left = Point { x: values[0].min(values[2]), y: values[1].min(values[3]) };
+    let bottom_right = Point { x: values[0].max(values[2]), y: values[1].max(values[3]) };
+
+    return (top_left, bottom_right);
+}
+
+/*----------------------------------------------------- get_reading_bounds - */
+
+// Smallest window containing every sensor and beacon
+
+fn get_reading_bounds(readings: &Vec<Reading>) -> (Point, Point) {
+
+    let mut top_left = Point { x: i32::MAX, y: i32::MAX };
+    let mut bottom_right = Point { x: i32::MIN, y: i32::MIN };
+
+    for r in readings {
+        for p in [r.sensor, r.beacon] {
+            top_left.x = top_left.x.min(p.x);
+            top_left.y = top_left.y.min(p.y);
+            bottom_right.x = bottom_right.x.max(p.x);
+            bottom_right.y = bottom_right.y.max(p.y);
+        }
+    }
+
+    return (top_left, bottom_right);
+}
+
+/*---------------------------------------------------- get_coverage_bounds - */
+
+// Smallest window containing every sensor's full area of coverage
+
+fn get_coverage_bounds(readings: &Vec<Reading>) -> (Point, Point) {
+
+    let mut top_left = Point { x: i32::MAX, y: i32::MAX };
+    let mut bottom_right = Point { x: i32::MIN, y: i32::MIN };
+
+    for r in readings {
+        let distance = r.distance() as i32;
+        top_left.x = top_left.x.min(r.sensor.x - distance);
+        top_left.y = top_left.y.min(r.sensor.y - distance);
+        bottom_right.x = bottom_right.x.max(r.sensor.x + distance);
+        bottom_right.y = bottom_right.y.max(r.sensor.y + distance);
+    }
+
+    return (top_left, bottom_right);
+}
+
+/*------------------------------------------------------------- draw_ascii - */
+
+// Draws the window in the puzzle's own format: S=sensor, B=beacon,
+// #=covered, *=uncovered cell inside the part 2 search area.
+
+fn draw_ascii(readings: &Vec<Reading>, top_left: Point, bottom_right: Point) {
+
+    let limit = get_search_limit();
+    let label_width = top_left.y.to_string().len().max(bottom_right.y.to_string().len());
+
+    for line in get_column_labels(top_left.x, bottom_right.x) {
+        applog!("{} {}", " ".repeat(label_width), line.trim_end());
+    }
+
+    for y in top_left.y..=bottom_right.y {
+        let mut row: String = String::new();
+
+        for x in top_left.x..=bottom_right.x {
+            let cell = Point { x, y };
+            let in_search_area = x >= 0 && x <= limit && y >= 0 && y <= limit;
+
+            let c = if readings.iter().any(|r| r.sensor == cell) {
+                'S'
+            } else if readings.iter().any(|r| r.beacon == cell) {
+                'B'
+            } else if is_covered(readings, cell) {
+                '#'
+            } else if in_search_area {
+                '*'
+            } else {
+                '.'
+            };
+            row.push(c);
+        }
+
+        let label = format!("{:>width$}", y, width = label_width);
+        applog!("{} {}", label, row);
+    }
+}
+
+/*------------------------------------------------------ get_column_labels - */
+
+// Column numbers for every fifth column, written vertically
+
+fn get_column_labels(start: i32, end: i32) -> Vec<String> {
+
+    let labels: Vec<String> = (start..=end)
+        .map(|x| if x % 5 == 0 {x.to_string()} else {String::new()})
+        .collect();
+    let height = labels.iter().map(|l| l.len()).max().unwrap_or(0);
+
+    let mut lines: Vec<String> = vec![];
+    for i in 0..height {
+        let line: String = labels.iter()
+            .map(|l| {
+                let padding = height - l.len();
+                if i < padding {' '} else {l.chars().nth(i - padding).unwrap()}
+            })
+            .collect();
+        lines.push(line);
+    }
+
+    return lines;
+}
+
+/*----------------------------------------------------------- write_bitmap - */
+
+// Each pixel covers a scale x scale block of cells, sampled at its centre.
+// Sensors and beacons are always drawn, whichever cell of the block they hit.
+
+fn write_bitmap(readings: &Vec<Reading>, filename: &str, top_left: Point, bottom_right: Point) {
+
+    let width = bottom_right.x as i64 - top_left.x as i64 + 1;
+    let height = bottom_right.y as i64 - top_left.y as i64 + 1;
+
+    let scale: i64 = match startup::value("scale") {
+        Some(s) => s.parse::<i64>().unwrap_or_else(|_| panic!("Invalid scale: [{}]", s)).max(1),
+        None => ((width.max(height) + DEFAULT_BITMAP_SIZE - 1) / DEFAULT_BITMAP_SIZE).max(1),
+    };
+
+    let pixel_width = ((width + scale - 1) / scale) as usize;
+    let pixel_height = ((height + scale - 1) / scale) as usize;
+    let mut pixels: Vec<u8> = vec![PIXEL_UNCOVERED; pixel_width * pixel_height];
+
+    for py in 0..pixel_height {
+        let y = (top_left.y as i64 + py as i64 * scale + scale / 2).min(bottom_right.y as i64) as i32;
+        let ranges = merge_sensor_ranges(&get_sensor_ranges_hitting_row(readings, y));
+
+        for px in 0..pixel_width {
+            let x = (top_left.x as i64 + px as i64 * scale + scale / 2).min(bottom_right.x as i64) as i32;
+            if ranges.iter().any(|r| r.contains(x)) {
+                pixels[py * pixel_width + px] = PIXEL_COVERED;
+            }
+        }
+    }
+
+    for r in readings {
+        for (p, level) in [(r.beacon, PIXEL_BEACON), (r.sensor, PIXEL_SENSOR)] {
+            if p.x < top_left.x || p.x > bottom_right.x || p.y < top_left.y || p.y > bottom_right.y {
+                continue;
+            }
+            let px = ((p.x as i64 - top_left.x as i64) / scale) as usize;
+            let py = ((p.y as i64 - top_left.y as i64) / scale) as usize;
+            pixels[py * pixel_width + px] = level;
+        }
+    }
+
+    let mut writer = BufWriter::new(File::create(filename).unwrap());
+    write!(writer, "P5\n{} {}\n255\n", pixel_width, pixel_height).unwrap();
+    writer.write_all(&pixels).unwrap();
+    writer.flush().unwrap();
+
+    applog!("Wrote {}x{} bitmap of ({},{})-({},{}) at 1:{} to {}",
+        pixel_width, pixel_height,
+        top_left.x, top_left.y, bottom_right.x, bottom_right.y,
+        scale, filename);
+}
+
+/*------------------------------------------------------------- is_covered - */
+
+fn is_covered(readings: &Vec<Reading>, cell: Point) -> bool {
+    return readings.iter().any(|r| {
+        let distance = (r.sensor.x - cell.x).abs() as u32 + (r.sensor.y - cell.y).abs() as u32;
+        distance <= r.distance()
+    });
+}
+
+/*------------------------------------------------------- End of render.rs - */
\ No newline at end of file
@@ -25,6 +25,11 @@ impl StartupInfo {
         let command = format!("-{}", option);
         return self.args.contains(&command);
     }
+    fn get_option_value(&self, option: &str) -> Option<String> {
+        let command = format!("-{}", option);
+        let index = self.args.iter().position(|a| *a == command)?;
+        return self.args.get(index+1).cloned();
+    }
     fn get_exe_name(&self) -> &str {
         return Path::new(&self.args[0]).file_stem().unwrap().to_str().unwrap();
     }
@@ -77,6 +82,14 @@ pub fn is(name: &str) -> bool {
     return APP_GLOBALS.get().unwrap().is_option_on(name);
 }
 
+/*------------------------------------------------------------------ value - */
+
+// Returns the argument following -[name], e.g. "-window 0,0,20,20"
+
+pub fn value(name: &str) -> Option<String> {
+    return APP_GLOBALS.get().unwrap().get_option_value(name);
+}
+
 /*---------------------------------------------------------- get_start_time - */
 
 pub fn get_start_time() -> Instant {